@@ -2,11 +2,22 @@
 // Each character has different states for the various actions
 
 use rand::seq::SliceRandom;
-use rand::thread_rng;
+use rand::{thread_rng, Rng};
 use serde::{Serialize, Deserialize};
+use clap::ValueEnum;
+use schemars::JsonSchema;
+
+// What the Nybbler is doing when it says something
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Moment {
+    Eating,
+    Playing,
+    Sleeping,
+    Healing,
+}
 
 // Character type for identifying different character designs
-#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize, JsonSchema, ValueEnum)]
 pub enum CharacterType {
     Blob,
     Square,
@@ -17,7 +28,7 @@ pub enum CharacterType {
 
 impl CharacterType {
     // Get a random character type
-    pub fn random() -> Self {
+    pub fn random<R: Rng>(rng: &mut R) -> Self {
         let types = [
             CharacterType::Blob,
             CharacterType::Square,
@@ -26,8 +37,12 @@ impl CharacterType {
             CharacterType::Robo,
         ];
         
-        let mut rng = thread_rng();
-        *types.choose(&mut rng).unwrap()
+        *types.choose(rng).unwrap()
+    }
+
+    // Get a random character type without a session RNG, used for old saves
+    pub fn from_entropy() -> Self {
+        Self::random(&mut thread_rng())
     }
     
     // Get the neutral state for this character type
@@ -239,4 +254,49 @@ impl CharacterType {
 "#,
         }
     }
-}
\ No newline at end of file
+
+    // Get a line of dialogue in this character's own voice
+    pub fn speak(&self, moment: Moment) -> &'static str {
+        match (self, moment) {
+            (CharacterType::Robo, Moment::Eating) => "FUEL. RECEIVED. EFFICIENCY: OPTIMAL.",
+            (CharacterType::Robo, Moment::Playing) => "FUN.EXE RUNNING. BEEP BOOP.",
+            (CharacterType::Robo, Moment::Sleeping) => "ENTERING. LOW. POWER. MODE...",
+            (CharacterType::Robo, Moment::Healing) => "DIAGNOSTICS: ALL SYSTEMS NOMINAL.",
+            (CharacterType::Cat, Moment::Eating) => "Mrrrow, that was purr-fectly delicious!",
+            (CharacterType::Cat, Moment::Playing) => "Meow! Pounce! Meow meow!",
+            (CharacterType::Cat, Moment::Sleeping) => "Purrr... dreaming of sunbeams... meow...",
+            (CharacterType::Cat, Moment::Healing) => "Meow, I feel like I have all nine lives again!",
+            (CharacterType::Ghost, Moment::Eating) => "Boo-licious! Even ghosts get hungry...",
+            (CharacterType::Ghost, Moment::Playing) => "Booo! Can't catch me, I float through walls!",
+            (CharacterType::Ghost, Moment::Sleeping) => "Haunting my dreams... ooOOoo...",
+            (CharacterType::Ghost, Moment::Healing) => "Back from the other side! Boo-yah!",
+            // Everyone else speaks plainly
+            (_, Moment::Eating) => "Yummy! That was delicious!",
+            (_, Moment::Playing) => "Bouncing around with joy!",
+            (_, Moment::Sleeping) => "Dreaming of treats and toys...",
+            (_, Moment::Healing) => "All better now! Healthy and strong!",
+        }
+    }
+}
+
+// Redraw character art for terminals without block characters, swapping each
+// glyph for a plain look-alike of the same width so the shape holds together
+pub fn plain_art(art: &str) -> String {
+    art.chars()
+        .map(|c| match c {
+            '█' => '#',
+            '▄' => '_',
+            '▀' => '~',
+            '▓' => '@',
+            '○' | '◕' | '□' => 'o',
+            '◡' | '‿' => 'u',
+            '◠' => 'n',
+            '▿' | '▼' => 'v',
+            '▽' => 'V',
+            '▲' => '^',
+            '♥' | '★' => '*',
+            'ᴥ' => 'w',
+            _ => c,
+        })
+        .collect()
+}
@@ -0,0 +1,269 @@
+// Player configuration loaded from config.json in the save directory
+// Every field has a default so a missing or partial config is fine
+
+use std::collections::BTreeMap;
+use std::fs;
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use crate::{get_save_directory, Action, Level, StartingProfile};
+
+// File name of the config inside the save directory
+pub const CONFIG_FILE: &str = "config.json";
+
+// Settings that change how the game feels without touching the code
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub decay_curve: DecayCurve,
+    // How many points each stat falls per hour before personality and other modifiers
+    pub hunger_decay_per_hour: f64,
+    pub happiness_decay_per_hour: f64,
+    pub energy_decay_per_hour: f64,
+    // Whether pets looked after in the same session cheer each other up
+    pub companionship: bool,
+    // Whether sick pets can pass their illness to neglected ones
+    pub contagion: bool,
+    // Whether to keep a diagnostic events.log next to the saves
+    pub log_events: bool,
+    // Which actions the menu offers, in order
+    pub menu: Vec<Action>,
+    // Names to show instead of the built-in action labels
+    pub menu_labels: BTreeMap<Action, String>,
+    // Whether the hunger stat is shown as fullness or as hunger
+    pub hunger_display: HungerDisplay,
+    // Starting stats for newly created pets
+    pub starting_profile: StartingProfile,
+    // Whether ages are always in days or in the most fitting unit
+    pub age_units: AgeUnits,
+    // Hours of an absence that pass without any decay
+    pub grace_hours: f64,
+    // Most pets to keep saved at once, or no limit
+    pub max_pets: Option<usize>,
+    // Actions the routine command does in one go, in order
+    pub routine: Vec<Action>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            decay_curve: DecayCurve::default(),
+            hunger_decay_per_hour: 5.0,
+            happiness_decay_per_hour: 3.0,
+            energy_decay_per_hour: 2.0,
+            companionship: true,
+            contagion: false,
+            log_events: false,
+            menu: Action::ALL.to_vec(),
+            menu_labels: BTreeMap::new(),
+            hunger_display: HungerDisplay::default(),
+            starting_profile: StartingProfile::default(),
+            age_units: AgeUnits::default(),
+            grace_hours: 3.0,
+            max_pets: None,
+            routine: vec![Action::Feed, Action::Play, Action::Sleep],
+        }
+    }
+}
+
+impl Config {
+    // Load the config, falling back to defaults if it is missing or malformed
+    pub fn load() -> Self {
+        let Ok(save_dir) = get_save_directory() else {
+            return Config::default();
+        };
+
+        let mut config = match fs::read_to_string(save_dir.join(CONFIG_FILE)) {
+            Ok(data) => serde_json::from_str(&data).unwrap_or_else(|e| {
+                eprintln!("Ignoring malformed {}: {}", CONFIG_FILE, e);
+                Config::default()
+            }),
+            Err(_) => Config::default(),
+        };
+        config.validate_menu();
+        config
+    }
+
+    // Make sure the player can always leave, however the menu was customized
+    fn validate_menu(&mut self) {
+        if !self.menu.contains(&Action::Exit) {
+            eprintln!("The menu in {} has no Exit, so it was added at the end.", CONFIG_FILE);
+            self.menu.push(Action::Exit);
+        }
+    }
+}
+
+// How quickly stats fall as time passes
+#[derive(Clone, Copy, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub enum DecayCurve {
+    // Stats fall at a constant rate
+    #[default]
+    Linear,
+    // Stats fall faster the lower they already are, up to twice as fast
+    Accelerating,
+    // Stats only fall as the clock passes each hour
+    Stepped,
+}
+
+impl DecayCurve {
+    // How much a stat currently at `current` falls over `hours` at `rate` per hour
+    pub fn decay(self, rate: f64, hours: f64, current: u8) -> f64 {
+        match self {
+            DecayCurve::Linear => rate * hours,
+            DecayCurve::Accelerating => {
+                let urgency = 1.0 + f64::from(100 - current.min(100)) / 100.0;
+                rate * hours * urgency
+            },
+            DecayCurve::Stepped => rate * hours.floor(),
+        }
+    }
+
+    // Hours of decay from `from` to `to` once the first `grace_hours` are over;
+    // the stepped curve counts the hours the clock strikes in that time
+    pub fn decay_hours(self, from: DateTime<Local>, to: DateTime<Local>, grace_hours: f64) -> f64 {
+        match self {
+            DecayCurve::Stepped => {
+                let clock = |at: DateTime<Local>| at.naive_local().and_utc().timestamp() as f64 / 3600.0;
+                (clock(to).floor() - (clock(from) + grace_hours).floor()).max(0.0)
+            },
+            _ => {
+                let hours = to.signed_duration_since(from).num_seconds() as f64 / 3600.0;
+                (hours - grace_hours).max(0.0)
+            },
+        }
+    }
+}
+
+// How the hunger stat is shown to the player
+// Saves always store fullness, where higher means better fed
+#[derive(Clone, Copy, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub enum HungerDisplay {
+    // Show the stored value, so a full pet reads 100
+    #[default]
+    Fullness,
+    // Show how hungry the pet is, so a full pet reads 0
+    Hunger,
+}
+
+impl HungerDisplay {
+    // Name to show next to the value
+    pub fn label(self) -> &'static str {
+        match self {
+            HungerDisplay::Fullness => "Fullness",
+            HungerDisplay::Hunger => "Hunger",
+        }
+    }
+
+    // Turn the stored fullness into the value to show
+    pub fn show(self, fullness: u8) -> u8 {
+        match self {
+            HungerDisplay::Fullness => fullness,
+            HungerDisplay::Hunger => 100 - fullness.min(100),
+        }
+    }
+
+    // Turn a value as shown back into the fullness to store
+    pub fn store(self, shown: u8) -> u8 {
+        // Inverting twice gets back to where it started
+        self.show(shown)
+    }
+
+    // Describe a level of fullness so it reads right for the shown value
+    pub fn describe(self, level: Level) -> &'static str {
+        level.describe(self == HungerDisplay::Hunger)
+    }
+}
+
+// How a Nybbler's age is written out
+#[derive(Clone, Copy, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub enum AgeUnits {
+    // Always count in days, like "45 days"
+    #[default]
+    Days,
+    // Use weeks or months once they fit, like "6 weeks (45 days)"
+    Humanized,
+}
+
+impl AgeUnits {
+    // Write out an age given in days
+    pub fn format(self, days: u16) -> String {
+        let (count, unit) = match (self, days) {
+            (AgeUnits::Humanized, 60..) => (days / 30, "month"),
+            (AgeUnits::Humanized, 14..) => (days / 7, "week"),
+            _ => return plural(days, "day"),
+        };
+        format!("{} ({})", plural(count, unit), plural(days, "day"))
+    }
+}
+
+// Count something, adding an "s" unless there is exactly one
+fn plural(count: u16, unit: &str) -> String {
+    if count == 1 {
+        format!("{} {}", count, unit)
+    } else {
+        format!("{} {}s", count, unit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn menu_without_exit_gets_it_back() {
+        let mut config = Config { menu: vec![Action::Feed, Action::Play], ..Config::default() };
+        config.validate_menu();
+        assert_eq!(config.menu, vec![Action::Feed, Action::Play, Action::Exit]);
+    }
+
+    #[test]
+    fn linear_decay_ignores_current_value() {
+        assert_eq!(DecayCurve::Linear.decay(5.0, 2.0, 100), 10.0);
+        assert_eq!(DecayCurve::Linear.decay(5.0, 2.0, 10), 10.0);
+    }
+
+    #[test]
+    fn accelerating_decay_speeds_up_as_stats_fall() {
+        assert_eq!(DecayCurve::Accelerating.decay(5.0, 1.0, 100), 5.0);
+        assert_eq!(DecayCurve::Accelerating.decay(5.0, 1.0, 50), 7.5);
+        assert_eq!(DecayCurve::Accelerating.decay(5.0, 1.0, 0), 10.0);
+    }
+
+    #[test]
+    fn stepped_decay_waits_for_whole_hours() {
+        assert_eq!(DecayCurve::Stepped.decay(5.0, 0.9, 80), 0.0);
+        assert_eq!(DecayCurve::Stepped.decay(5.0, 2.5, 80), 10.0);
+    }
+
+    #[test]
+    fn stepped_decay_counts_the_hours_the_clock_strikes() {
+        let at = |hour, minute| Local.with_ymd_and_hms(2024, 3, 1, hour, minute, 0).unwrap();
+        assert_eq!(DecayCurve::Stepped.decay_hours(at(10, 50), at(11, 10), 0.0), 1.0);
+        assert_eq!(DecayCurve::Stepped.decay_hours(at(10, 5), at(10, 55), 0.0), 0.0);
+        assert_eq!(DecayCurve::Stepped.decay_hours(at(10, 50), at(12, 10), 1.0), 1.0);
+        assert_eq!(DecayCurve::Linear.decay_hours(at(10, 0), at(12, 30), 1.0), 1.5);
+    }
+
+    #[test]
+    fn humanized_ages_switch_units_as_pets_grow() {
+        assert_eq!(AgeUnits::Days.format(400), "400 days");
+        assert_eq!(AgeUnits::Humanized.format(1), "1 day");
+        assert_eq!(AgeUnits::Humanized.format(13), "13 days");
+        assert_eq!(AgeUnits::Humanized.format(15), "2 weeks (15 days)");
+        assert_eq!(AgeUnits::Humanized.format(75), "2 months (75 days)");
+    }
+
+    #[test]
+    fn hunger_display_can_invert_fullness() {
+        assert_eq!(HungerDisplay::Fullness.show(80), 80);
+        assert_eq!(HungerDisplay::Hunger.show(80), 20);
+        assert_eq!(HungerDisplay::Hunger.show(100), 0);
+        assert_eq!(HungerDisplay::Hunger.store(HungerDisplay::Hunger.show(30)), 30);
+        assert_eq!(HungerDisplay::Hunger.describe(Level::CriticallyLow), "critically high");
+        assert_eq!(HungerDisplay::Hunger.describe(Level::Good), "low");
+        assert_eq!(HungerDisplay::Fullness.describe(Level::Low), "low");
+    }
+}
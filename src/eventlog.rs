@@ -0,0 +1,45 @@
+// Optional diagnostic log of what the simulation did, for filing bug reports
+// Appends to events.log in the save directory; failures never stop the game
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use chrono::Local;
+
+use crate::get_save_directory;
+
+// File name of the log inside the save directory
+pub const LOG_FILE: &str = "events.log";
+
+// Size at which the log is rotated to keep it from growing forever
+const MAX_LOG_BYTES: u64 = 512 * 1024;
+
+// Whether logging was turned on for this run
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+// Turn logging on for the rest of the run
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+// Append a timestamped line to the log if logging is on
+pub fn record(message: &str) {
+    if ENABLED.load(Ordering::Relaxed) {
+        // The log is only diagnostic, so a failed write is silently dropped
+        let _ = append(message);
+    }
+}
+
+fn append(message: &str) -> std::io::Result<()> {
+    let save_dir = get_save_directory()?;
+    let path = save_dir.join(LOG_FILE);
+
+    // Keep one previous log around and start a fresh one
+    if fs::metadata(&path).is_ok_and(|metadata| metadata.len() >= MAX_LOG_BYTES) {
+        fs::rename(&path, save_dir.join(format!("{}.1", LOG_FILE)))?;
+    }
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{} {}", Local::now().to_rfc3339(), message)
+}
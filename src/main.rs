@@ -1,21 +1,215 @@
 use std::time::Duration;
 use std::thread;
-use std::path::PathBuf;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::borrow::Cow;
+use std::env;
+use std::ffi::OsString;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::path::{Path, PathBuf};
 use std::fs;
-use std::io::{self, ErrorKind};
+use std::io::{self, ErrorKind, IsTerminal};
 use std::process;
 use dialoguer::{Select, theme::ColorfulTheme};
 use indicatif::{ProgressBar, ProgressStyle};
-use console::{Term, style};
-use chrono::{DateTime, Local};
+use console::{Color, Term, style};
+use chrono::{DateTime, Datelike, Local, NaiveDate};
 use serde::{Serialize, Deserialize};
+use schemars::JsonSchema;
 use dirs::data_dir;
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use characters::Moment;
+use config::{Config, HungerDisplay};
+use render::{BarLayout, Render};
 
 mod characters;
+mod config;
+mod eventlog;
+mod render;
+#[cfg(feature = "image")]
+mod photo;
+#[cfg(feature = "server")]
+mod server;
+mod tui;
+
+// Environment variable naming a folder to keep saves in instead of the usual one
+const DATA_DIR_ENV: &str = "NYBBLER_DATA_DIR";
+
+// Save folder in the working directory when the platform has no data directory
+const FALLBACK_SAVE_DIR: &str = ".nybbler";
+
+// Shown by read-only commands before any pet has been created
+const NO_PETS_HINT: &str = "You don't have any Nybblers yet — run `nybbler` to create one.";
+
+// Exit code for commands asked about a pet that doesn't exist
+const EXIT_NOT_FOUND: i32 = 1;
+
+// Exit code when the game needs to prompt but input isn't a terminal
+const EXIT_NO_TERMINAL: i32 = 2;
+
+// Names offered when the player asks for a random one
+const PET_NAMES: &[&str] = &[
+    "Biscuit", "Mochi", "Pickle", "Noodle", "Pixel", "Sprout", "Bubbles", "Waffles",
+    "Pudding", "Sprocket", "Dumpling", "Nibbles", "Gizmo", "Muffin", "Pebble", "Tofu",
+];
+
+// Stat level above which a Nybbler counts as well cared for
+const HEALTHY_STAT_THRESHOLD: u8 = 60;
+
+// Health regained per hour while a Nybbler is well cared for
+const HEALTH_RECOVERY_PER_HOUR: f64 = 2.0;
+
+// Default stat level below which a stat counts as critical
+const DEFAULT_ALERT_THRESHOLD: u8 = 20;
+
+// Minimum seconds between updates before any time-based changes apply
+const MIN_UPDATE_INTERVAL_SECS: i64 = 60;
+
+// Widest a name is drawn in headers and messages, in terminal columns
+const NAME_DISPLAY_WIDTH: usize = 24;
+
+// Number of mood changes remembered in a Nybbler's history
+const MOOD_HISTORY_LEN: usize = 100;
+
+// Number of actions remembered for the diary
+const EVENT_HISTORY_LEN: usize = 200;
+
+// Number of stat snapshots remembered for the timelapse
+const SNAPSHOT_HISTORY_LEN: usize = 200;
+
+// Snapshots closer together than this replace each other instead of piling up
+const SNAPSHOT_MIN_INTERVAL_SECS: i64 = 300;
+
+// How long read-only commands wait before reading a half-written save again
+const READ_RETRY_MILLIS: u64 = 100;
+
+// How long each timelapse frame stays on screen
+const TIMELAPSE_FRAME_MILLIS: u64 = 400;
+
+// Health below which the character art pulses like a faint heartbeat
+const HEARTBEAT_HEALTH: u8 = 15;
+
+// How many beats play each time the stats are shown, and how long each half lasts
+const HEARTBEAT_BEATS: usize = 2;
+const HEARTBEAT_MILLIS: u64 = 350;
+
+// Hours of continuous sadness or sickness before a Nybbler becomes depressed
+const DEPRESSION_HOURS: f64 = 24.0;
+
+// Hunger and happiness needed to pull a Nybbler out of depression
+const DEPRESSION_RECOVERY: u8 = 60;
+
+// Happiness a treat gives when the Nybbler hasn't had one recently
+const TREAT_HAPPINESS: u8 = 8;
+
+// Happiness lost from each treat for every recent treat before it
+const TREAT_FALLOFF: u8 = 2;
+
+// Recent treats after which another one causes a sugar crash
+const SUGAR_CRASH_TREATS: u8 = 4;
+
+// Health lost in a sugar crash
+const SUGAR_CRASH_HEALTH: u8 = 5;
+
+// Starting value and multiplier of 64-bit FNV-1a hashing
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+// Hours for the Nybbler to forget about one recent treat
+const TREAT_RECOVERY_HOURS: f64 = 1.0;
+
+// Widest the notes are shown in compact views before being truncated
+const NOTES_PREVIEW_WIDTH: usize = 40;
+
+// Share of missing energy a starving Nybbler still recovers from sleep
+const SLEEP_MIN_QUALITY: f64 = 0.3;
+
+// How fast stats fall for a Nybbler that has survived to adulthood
+const ADULT_DECAY_MULTIPLIER: f64 = 0.8;
+
+// Chance that a sick Nybbler passes its illness to a neglected pet nearby
+const CONTAGION_CHANCE: f64 = 0.15;
+
+// Health a Nybbler loses when it catches something from another pet
+const CONTAGION_HEALTH: u8 = 15;
+
+// Happiness a Nybbler gains from spending time with another pet
+const COMPANY_HAPPINESS: u8 = 3;
+
+// Hours before a Nybbler can enjoy company again, so switching can't be farmed
+const COMPANY_COOLDOWN_HOURS: i64 = 6;
+
+// Energy a Nybbler needs before it's willing to play
+const PLAY_MIN_ENERGY: u8 = 10;
+
+// Minutes before the Nybbler can nap again
+const SLEEP_COOLDOWN_MINUTES: i64 = 30;
+
+// Minutes before the Nybbler can take more medicine
+const HEAL_COOLDOWN_MINUTES: i64 = 60;
+
+// Chance that comforting lifts a sad or sick mood right away
+const COMFORT_LIFT_CHANCE: f64 = 0.3;
+
+// Extra happiness from doing the Nybbler's favorite activity
+const FAVORITE_ACTIVITY_BONUS: u8 = 5;
+
+// Hours without the favorite activity before the Nybbler sulks
+const FAVORITE_NEGLECT_HOURS: i64 = 24;
+
+// Happiness lost when the favorite activity is neglected
+const FAVORITE_NEGLECT_PENALTY: u8 = 5;
+
+// Days in a row of care an Elder needs before it can graduate
+const GRADUATION_STREAK_DAYS: u32 = 7;
+
+// Wellness every recent snapshot must reach for an Elder to graduate
+const GRADUATION_WELLNESS: u8 = 70;
+
+// Minutes between updates after which the player counts as having been away
+const ABSENCE_MIN_MINUTES: i64 = 15;
+
+// Health lost per update while hunger or happiness is very low
+const NEGLECT_HEALTH_LOSS: u8 = 5;
+
+// Health a brave Nybbler loses instead while neglected
+const BRAVE_NEGLECT_HEALTH_LOSS: u8 = 3;
+
+// Extra happiness a gluttonous Nybbler gets from a meal
+const GLUTTON_FEED_HAPPINESS: u8 = 5;
+
+// Happiness a shy Nybbler loses when you switch to another pet
+const SHY_LEFT_BEHIND_HAPPINESS: u8 = 5;
+
+// Cleanliness lost per hour, and the happiness a bath brings
+const CLEANLINESS_DECAY_PER_HOUR: f64 = 2.0;
+const CLEAN_HAPPINESS: u8 = 5;
+
+// Experience earned for each action, and per level needed to reach the next one
+const XP_PER_ACTION: u32 = 10;
+const XP_PER_LEVEL: u32 = 50;
+
+// Percent more each action helps per level above the first, and the most it can add
+const LEVEL_BONUS_PERCENT: u16 = 2;
+const MAX_LEVEL_BONUS_PERCENT: u16 = 20;
+
+// Bond a care action builds, at most once per this many hours so it rewards steady care
+const BOND_CARE_GAIN: u8 = 1;
+const BOND_GAIN_HOURS: i64 = 4;
+
+// Bond built by comforting a Nybbler when it's down
+const BOND_COMFORT_GAIN: u8 = 3;
+
+// Bond lost for each whole day the Nybbler is left alone
+const BOND_LOSS_PER_DAY: u8 = 1;
+
+// Format version written into new saves; older ones are migrated on load
+const SAVE_VERSION: u32 = 1;
 
 // States that the Nybbler can be in
-#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize, JsonSchema)]
 enum NybblerMood {
     Happy,
     Neutral,
@@ -24,10 +218,11 @@ enum NybblerMood {
     Sleeping,
     Excited,
     Playful,
+    Depressed,
 }
 
 impl NybblerMood {
-    fn to_emoji(&self) -> &str {
+    fn to_emoji(self) -> &'static str {
         match self {
             NybblerMood::Happy => "😊",
             NybblerMood::Neutral => "😐",
@@ -36,6 +231,40 @@ impl NybblerMood {
             NybblerMood::Sleeping => "😴",
             NybblerMood::Excited => "🤩",
             NybblerMood::Playful => "😋",
+            NybblerMood::Depressed => "😞",
+        }
+    }
+
+    // A plain-text face for terminals without emoji
+    fn to_ascii(self) -> &'static str {
+        match self {
+            NybblerMood::Happy => ":)",
+            NybblerMood::Neutral => ":|",
+            NybblerMood::Sad => ":(",
+            NybblerMood::Sick => "x_x",
+            NybblerMood::Sleeping => "-_-",
+            NybblerMood::Excited => ":D",
+            NybblerMood::Playful => ":P",
+            NybblerMood::Depressed => ":'(",
+        }
+    }
+
+    // The emoji or plain face, whichever the terminal can show
+    fn glyph(self, render: Render) -> &'static str {
+        render.pick(self.to_emoji(), self.to_ascii())
+    }
+
+    // What the Nybbler says about how it feels
+    fn message(self, render: Render) -> &'static str {
+        match self {
+            NybblerMood::Happy => render.pick("💖 I'm happy! 💖", "I'm happy!"),
+            NybblerMood::Neutral => render.pick("🌱 I'm doing okay. 🌱", "I'm doing okay."),
+            NybblerMood::Sad => render.pick("💧 I'm feeling sad... 💧", "I'm feeling sad..."),
+            NybblerMood::Sick => render.pick("🌡️ I don't feel well... 💊", "I don't feel well..."),
+            NybblerMood::Sleeping => render.pick("💤 Zzz... 💤", "Zzz..."),
+            NybblerMood::Excited => render.pick("✨ I'm super excited! ✨", "I'm super excited!"),
+            NybblerMood::Playful => render.pick("🎮 Let's play! 🎮", "Let's play!"),
+            NybblerMood::Depressed => render.pick("🌧️ Nothing feels fun anymore... 🌧️", "Nothing feels fun anymore..."),
         }
     }
 
@@ -49,508 +278,5860 @@ impl NybblerMood {
             NybblerMood::Sleeping => vec!["(-.-)zzz", "(-_-)zzz", "(-.-)zzz", "(-_-)zzz"],
             NybblerMood::Excited => vec!["(★^O^★)", "(☆^ー^☆)", "(★^O^★)", "(☆^ー^☆)"],
             NybblerMood::Playful => vec!["(◕ᗜ◕✿)", "(◠‿◠✿)", "(◕ᗜ◕✿)", "(◠‿◠✿)"],
+            NybblerMood::Depressed => vec!["(._.)", "(._.)", "(._.)", "(。_。)"],
+        }
+    }
+
+    // Describe the mood in a few plain words
+    fn describe(self) -> &'static str {
+        match self {
+            NybblerMood::Happy => "happy",
+            NybblerMood::Neutral => "doing okay",
+            NybblerMood::Sad => "sad",
+            NybblerMood::Sick => "sick",
+            NybblerMood::Sleeping => "sleepy",
+            NybblerMood::Excited => "excited",
+            NybblerMood::Playful => "playful",
+            NybblerMood::Depressed => "depressed",
         }
     }
+
+    // Whether this mood counts towards sustained neglect
+    fn is_low(self) -> bool {
+        matches!(self, NybblerMood::Sad | NybblerMood::Sick | NybblerMood::Depressed)
+    }
 }
 
-// The Nybbler struct to hold the game state
-#[derive(Serialize, Deserialize)]
-struct Nybbler {
-    name: String,
-    hunger: u8,
-    happiness: u8,
-    energy: u8,
-    health: u8,
-    age: u16,
-    #[serde(with = "chrono_serde")]
-    last_updated: DateTime<Local>,
-    mood: NybblerMood,
-    #[serde(default = "characters::CharacterType::random")]
-    character_type: characters::CharacterType,
+// The core stats every Nybbler has
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Stat {
+    Hunger,
+    Happiness,
+    Energy,
+    Health,
+    Cleanliness,
 }
 
-// Helper module to serialize/deserialize chrono::DateTime
-mod chrono_serde {
-    use chrono::{DateTime, Local};
-    use serde::{self, Deserialize, Deserializer, Serializer};
+impl Stat {
+    // Every stat, in display order
+    const ALL: [Stat; 5] = [Stat::Hunger, Stat::Happiness, Stat::Energy, Stat::Health, Stat::Cleanliness];
 
-    pub fn serialize<S>(date: &DateTime<Local>, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        let s = date.to_rfc3339();
-        serializer.serialize_str(&s)
+    // Get the display name for this stat
+    fn name(self) -> &'static str {
+        match self {
+            Stat::Hunger => "Hunger",
+            Stat::Happiness => "Happiness",
+            Stat::Energy => "Energy",
+            Stat::Health => "Health",
+            Stat::Cleanliness => "Cleanliness",
+        }
     }
 
-    pub fn deserialize<'de, D>(deserializer: D) -> Result<DateTime<Local>, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        let s = String::deserialize(deserializer)?;
-        let dt = DateTime::parse_from_rfc3339(&s)
-            .map_err(serde::de::Error::custom)?
-            .with_timezone(&Local);
-        Ok(dt)
+    // Explain what this stat means and what moves it
+    fn meaning(self, render: Render) -> &'static str {
+        match (self, render.hunger) {
+            (Stat::Hunger, HungerDisplay::Fullness) => "how well fed your pet is; falls over time, feeding raises it",
+            (Stat::Hunger, HungerDisplay::Hunger) => "how hungry your pet is; rises over time, feeding lowers it",
+            (Stat::Happiness, _) => "falls over time; playing, treats, and comfort raise it",
+            (Stat::Energy, _) => "falls over time and with play; sleep restores it, less so when hungry",
+            (Stat::Health, _) => "falls while your pet is hungry or unhappy; medicine restores it",
+            (Stat::Cleanliness, _) => "falls over time, and a dirty pet below 20 loses health; a bath restores it",
+        }
     }
-}
 
-impl Nybbler {
-    // Create a new Nybbler with default values
-    fn new(name: String) -> Self {
-        Nybbler {
-            name,
-            hunger: 50,
-            happiness: 50,
-            energy: 100,
-            health: 100,
-            age: 0,
-            last_updated: Local::now(),
-            mood: NybblerMood::Happy,
-            character_type: characters::CharacterType::random(),
+    // Get the name to show for this stat, which for hunger depends on the config
+    fn label(self, render: Render) -> &'static str {
+        match self {
+            Stat::Hunger => render.hunger.label(),
+            _ => self.name(),
         }
     }
 
-    // Save the Nybbler state to a file
-    fn save(&self) -> io::Result<()> {
-        let save_dir = get_save_directory()?;
-        let save_path = save_dir.join(format!("{}.json", self.name.to_lowercase()));
+    // Width of the longest label, so rows of stats line up
+    fn label_width(render: Render) -> usize {
+        Stat::ALL.iter().map(|stat| stat.label(render).len()).max().unwrap_or(0)
+    }
+}
 
-        let json = serde_json::to_string_pretty(self)
-            .map_err(|e| io::Error::new(ErrorKind::Other, e))?;
+// Actions the player can choose from the menu
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug, Serialize, Deserialize, JsonSchema)]
+enum Action {
+    Feed,
+    Treat,
+    Play,
+    Sleep,
+    Heal,
+    Clean,
+    Comfort,
+    Notes,
+    SwitchPet,
+    Help,
+    Exit,
+}
+
+impl Action {
+    // Every action, in menu order
+    const ALL: [Action; 11] = [
+        Action::Feed,
+        Action::Treat,
+        Action::Play,
+        Action::Sleep,
+        Action::Heal,
+        Action::Clean,
+        Action::Comfort,
+        Action::Notes,
+        Action::SwitchPet,
+        Action::Help,
+        Action::Exit,
+    ];
 
-        fs::write(save_path, json)
+    // How long the Nybbler needs before this action can be repeated
+    fn cooldown(self) -> Option<chrono::Duration> {
+        match self {
+            Action::Sleep => Some(chrono::Duration::minutes(SLEEP_COOLDOWN_MINUTES)),
+            Action::Heal => Some(chrono::Duration::minutes(HEAL_COOLDOWN_MINUTES)),
+            _ => None,
+        }
     }
 
-    // Load a Nybbler from a file
-    fn load(name: &str) -> io::Result<Self> {
-        let save_dir = get_save_directory()?;
-        let save_path = save_dir.join(format!("{}.json", name.to_lowercase()));
+    // How the diary describes doing this action, if it is worth writing down
+    fn past_tense(self) -> Option<&'static str> {
+        match self {
+            Action::Feed => Some("ate"),
+            Action::Treat => Some("had a treat"),
+            Action::Play => Some("played"),
+            Action::Sleep => Some("napped"),
+            Action::Heal => Some("took medicine"),
+            Action::Clean => Some("had a bath"),
+            Action::Comfort => Some("was comforted"),
+            Action::Notes | Action::SwitchPet | Action::Help | Action::Exit => None,
+        }
+    }
 
-        let data = fs::read_to_string(save_path)?;
-        let nybbler: Nybbler = serde_json::from_str(&data)
-            .map_err(|e| io::Error::new(ErrorKind::Other, e))?;
+    // Summarize what this action does to the Nybbler's stats
+    fn preview(self, render: Render) -> Option<String> {
+        match self {
+            Action::Feed => Some(FEED_EFFECT.describe(render)),
+            Action::Treat => Some(format!("up to +{} happiness", TREAT_HAPPINESS)),
+            Action::Play => Some(PLAY_EFFECT.describe(render)),
+            Action::Sleep => Some(format!("restores energy, {}", SLEEP_EFFECT.describe(render))),
+            Action::Heal => Some("restores health".to_string()),
+            Action::Clean => Some(format!("restores cleanliness, +{} happiness", CLEAN_HAPPINESS)),
+            Action::Comfort => Some(format!("{}, may lift its mood", COMFORT_EFFECT.describe(render))),
+            Action::Notes | Action::SwitchPet | Action::Help | Action::Exit => None,
+        }
+    }
 
-        Ok(nybbler)
+    // Explain what this action does and what it costs, for the help guide
+    fn guide(self, render: Render) -> String {
+        let mut guide = match self {
+            Action::Treat => format!(
+                "+{} happiness, {} less for each recent treat; more than {} in a row cost {} health",
+                TREAT_HAPPINESS, TREAT_FALLOFF, SUGAR_CRASH_TREATS, SUGAR_CRASH_HEALTH
+            ),
+            Action::Play => format!("{}, needs at least {} energy", PLAY_EFFECT.describe(render), PLAY_MIN_ENERGY),
+            Action::Comfort => format!("{}, only when sad or sick", self.preview(render).unwrap_or_default()),
+            Action::Notes => "write down anything about your pet".to_string(),
+            Action::SwitchPet => "look after another of your pets".to_string(),
+            Action::Help => "show this guide".to_string(),
+            Action::Exit => "save and leave".to_string(),
+            _ => self.preview(render).unwrap_or_default(),
+        };
+        if let Some(cooldown) = self.cooldown() {
+            guide.push_str(&format!(", then wait {}", format_remaining(cooldown)));
+        }
+        guide
     }
 
-    // Check if a save file exists for a Nybbler
-    fn save_exists(name: &str) -> bool {
-        if let Ok(save_dir) = get_save_directory() {
-            let save_path = save_dir.join(format!("{}.json", name.to_lowercase()));
-            save_path.exists()
-        } else {
-            false
+    // Get the menu label for this action, with a preview of its effects
+    fn menu_label(self, render: Render, config: &Config) -> String {
+        match self.preview(render) {
+            Some(preview) => format!("{} ({})", self.display_label(render, config), preview),
+            None => self.display_label(render, config),
         }
     }
 
-    // Update the Nybbler's stats based on elapsed time
-    fn update(&mut self) {
-        let now = Local::now();
-        let diff = now.signed_duration_since(self.last_updated);
-        let hours_passed = diff.num_seconds() as f64 / 3600.0;
+    // Get the label shown for this action, using the player's name for it if they set one
+    fn display_label(self, render: Render, config: &Config) -> String {
+        match config.menu_labels.get(&self) {
+            Some(label) => label.clone(),
+            None => self.label(render),
+        }
+    }
 
-        // Decrease stats based on time
-        let hunger_decrease = (5.0 * hours_passed).min(5.0) as u8;
-        let happiness_decrease = (3.0 * hours_passed).min(3.0) as u8;
-        let energy_decrease = (2.0 * hours_passed).min(2.0) as u8;
+    // Get the menu label for this action
+    fn label(self, render: Render) -> String {
+        match self {
+            Action::Feed => render.labeled("🍔", "Feed"),
+            Action::Treat => render.labeled("🍬", "Treat"),
+            Action::Play => render.labeled("🎮", "Play"),
+            Action::Sleep => render.labeled("💤", "Sleep"),
+            Action::Heal => render.labeled("💊", "Heal"),
+            Action::Clean => render.labeled("🧼", "Clean"),
+            Action::Comfort => render.labeled("🫂", "Comfort"),
+            Action::Notes => render.labeled("📝", "Edit notes"),
+            Action::SwitchPet => render.labeled("🔄", "Switch pet"),
+            Action::Help => render.labeled("❓", "Help"),
+            Action::Exit => render.labeled("👋", "Exit"),
+        }
+    }
+}
 
-        // Apply decreases, ensuring we don't underflow
-        self.hunger = self.hunger.saturating_sub(hunger_decrease);
-        self.happiness = self.happiness.saturating_sub(happiness_decrease);
-        self.energy = self.energy.saturating_sub(energy_decrease);
+// Stat changes an action applies
+#[derive(Clone, Copy)]
+struct ActionEffect {
+    hunger: i8,
+    happiness: i8,
+    energy: i8,
+    health: i8,
+}
 
-        // Update age (1 day every 24 real hours)
-        self.age += (hours_passed / 24.0) as u16;
+// Why an action can't be performed right now
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum ActionError {
+    // The action was done recently and can't be repeated for a while
+    CoolingDown(chrono::Duration),
+    // The Nybbler is too worn out for it
+    TooTired,
+    // Only a sad or sick Nybbler wants comforting
+    NotNeeded,
+}
 
-        // Update health based on hunger and happiness
-        if self.hunger < 20 || self.happiness < 20 {
-            self.health = self.health.saturating_sub(5);
+impl ActionError {
+    // A few words to annotate the menu entry with
+    fn short(self) -> String {
+        match self {
+            ActionError::CoolingDown(remaining) => format!("ready in {}", format_remaining(remaining)),
+            ActionError::TooTired => "too tired".to_string(),
+            ActionError::NotNeeded => "not needed".to_string(),
         }
+    }
 
-        // Update mood based on stats
-        self.update_mood();
-
-        // Update timestamp
-        self.last_updated = now;
+    // Explain to the player why the action was refused
+    fn reason(self, name: &str) -> String {
+        match self {
+            ActionError::CoolingDown(remaining) => {
+                format!("{} isn't ready for that again yet. Try again in {}.", name, format_remaining(remaining))
+            },
+            ActionError::TooTired => format!("{} is too tired to play. Let them sleep first!", name),
+            ActionError::NotNeeded => format!("{} is feeling fine and doesn't need comforting.", name),
+        }
     }
+}
 
-    // Update the Nybbler's mood based on its stats
-    fn update_mood(&mut self) {
-        if self.health < 30 {
-            self.mood = NybblerMood::Sick;
-        } else if self.energy < 20 {
-            self.mood = NybblerMood::Sleeping;
-        } else if self.hunger < 30 || self.happiness < 30 {
-            self.mood = NybblerMood::Sad;
-        } else if self.hunger > 70 && self.happiness > 70 && self.energy > 70 {
-            self.mood = NybblerMood::Excited;
-        } else if self.hunger > 70 && self.happiness > 70 {
-            self.mood = NybblerMood::Happy;
-        } else if self.happiness > 80 {
-            self.mood = NybblerMood::Playful;
-        } else {
-            self.mood = NybblerMood::Neutral;
+// Feeding fills the Nybbler up and gives a little energy
+const FEED_EFFECT: ActionEffect = ActionEffect { hunger: 30, happiness: 0, energy: 5, health: 0 };
+
+// Playing is fun but tiring and works up an appetite
+const PLAY_EFFECT: ActionEffect = ActionEffect { hunger: -10, happiness: 20, energy: -15, health: 0 };
+
+// Sleeping cheers the Nybbler up; the energy it restores depends on hunger
+const SLEEP_EFFECT: ActionEffect = ActionEffect { hunger: 0, happiness: 5, energy: 0, health: 0 };
+
+// Comforting a sad or sick Nybbler cheers it up a little
+const COMFORT_EFFECT: ActionEffect = ActionEffect { hunger: 0, happiness: 10, energy: 0, health: 0 };
+
+// Toys to play with, each as fun and as tiring as its own effect
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+enum Toy {
+    // Playing without a toy keeps the classic effect
+    #[default]
+    JustPlay,
+    Ball,
+    Puzzle,
+    Plushie,
+}
+
+impl Toy {
+    // Every toy, in menu order
+    const ALL: [Toy; 4] = [Toy::JustPlay, Toy::Ball, Toy::Puzzle, Toy::Plushie];
+
+    // How playing with this toy changes the Nybbler's stats
+    fn effect(self) -> ActionEffect {
+        match self {
+            Toy::JustPlay => PLAY_EFFECT,
+            // Chasing a ball is the most fun but wears the Nybbler out
+            Toy::Ball => ActionEffect { hunger: -15, happiness: 25, energy: -25, health: 0 },
+            // Puzzles are calm and keep the mind busy
+            Toy::Puzzle => ActionEffect { hunger: -5, happiness: 15, energy: -10, health: 0 },
+            // Cuddling a plushie is gentle enough for a tired Nybbler
+            Toy::Plushie => ActionEffect { hunger: -5, happiness: 10, energy: -5, health: 0 },
         }
     }
 
-    // Feed the Nybbler
-    fn feed(&mut self) {
-        self.hunger = (self.hunger + 30).min(100);
-        self.energy = (self.energy + 5).min(100);
-        self.update_mood();
+    // Get the menu label for this toy, with a preview of its effects
+    fn menu_label(self, render: Render) -> String {
+        let label = match self {
+            Toy::JustPlay => render.labeled("🎮", "Just play"),
+            Toy::Ball => render.labeled("⚽", "Ball"),
+            Toy::Puzzle => render.labeled("🧩", "Puzzle"),
+            Toy::Plushie => render.labeled("🧸", "Plushie"),
+        };
+        format!("{} ({})", label, self.effect().describe(render))
     }
 
-    // Play with the Nybbler
-    fn play(&mut self) {
-        self.happiness = (self.happiness + 20).min(100);
-        self.hunger = self.hunger.saturating_sub(10);
-        self.energy = self.energy.saturating_sub(15);
-        self.update_mood();
+    // What the toy is called in messages, if there is one
+    fn name(self) -> Option<&'static str> {
+        match self {
+            Toy::JustPlay => None,
+            Toy::Ball => Some("ball"),
+            Toy::Puzzle => Some("puzzle"),
+            Toy::Plushie => Some("plushie"),
+        }
     }
+}
 
-    // Put the Nybbler to sleep
-    fn sleep(&mut self) {
-        self.energy = 100;
-        self.happiness = (self.happiness + 5).min(100);
-        self.update_mood();
+impl ActionEffect {
+    // Apply the changes, keeping stats from wrapping or going above 100
+    fn apply(self, nybbler: &mut Nybbler) {
+        // Experienced pets get a little more out of the good parts of each action
+        let bonus = nybbler.level_bonus_percent();
+        let shift = |stat: u8, delta: i8| {
+            if delta >= 0 {
+                let boosted = (u16::from(delta as u8) * (100 + bonus) + 50) / 100;
+                stat.saturating_add(boosted.min(100) as u8).min(100)
+            } else {
+                stat.saturating_sub(delta.unsigned_abs())
+            }
+        };
+        nybbler.hunger = shift(nybbler.hunger, self.hunger);
+        nybbler.happiness = shift(nybbler.happiness, self.happiness);
+        nybbler.energy = shift(nybbler.energy, self.energy);
+        nybbler.health = shift(nybbler.health, self.health);
     }
 
-    // Heal the Nybbler
-    fn heal(&mut self) {
-        self.health = 100;
-        self.update_mood();
+    // Describe the non-zero changes, like "+30 fullness, +5 energy"
+    fn describe(self, render: Render) -> String {
+        // Hunger moves the other way from fullness
+        let hunger = match render.hunger {
+            HungerDisplay::Fullness => self.hunger,
+            HungerDisplay::Hunger => -self.hunger,
+        };
+        let hunger_name = render.hunger.label().to_lowercase();
+        [(hunger_name.as_str(), hunger), ("happiness", self.happiness), ("energy", self.energy), ("health", self.health)]
+            .iter()
+            .filter(|(_, delta)| *delta != 0)
+            .map(|(name, delta)| format!("{:+} {}", delta, name))
+            .collect::<Vec<_>>()
+            .join(", ")
     }
+}
 
-    // Check if the Nybbler is alive
-    fn is_alive(&self) -> bool {
-        self.health > 0
+// Activities a Nybbler can have a soft spot for
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Activity {
+    Eat,
+    Play,
+    Sleep,
+}
+
+impl Activity {
+    // Describe the activity for display
+    fn describe(self) -> &'static str {
+        match self {
+            Activity::Eat => "eating",
+            Activity::Play => "playing",
+            Activity::Sleep => "sleeping",
+        }
     }
 }
 
-// Helper function to get the save directory
-fn get_save_directory() -> io::Result<PathBuf> {
-    let mut save_dir = data_dir()
-        .ok_or_else(|| io::Error::new(ErrorKind::NotFound, "Could not find data directory"))?;
+// A mood change recorded in the Nybbler's history
+#[derive(Clone, Serialize, Deserialize, JsonSchema)]
+struct MoodEntry {
+    #[serde(with = "chrono_serde")]
+    #[schemars(with = "String")]
+    at: DateTime<Local>,
+    mood: NybblerMood,
+}
 
-    save_dir.push("nybbler");
+// An action the player performed, recorded for the diary
+#[derive(Clone, Serialize, Deserialize, JsonSchema)]
+struct ActionEvent {
+    #[serde(with = "chrono_serde")]
+    #[schemars(with = "String")]
+    at: DateTime<Local>,
+    action: Action,
+}
 
-    if !save_dir.exists() {
-        fs::create_dir_all(&save_dir)?;
-    }
+// The Nybbler's stats at a moment in time, used to replay its life
+#[derive(Clone, Serialize, Deserialize, JsonSchema)]
+struct StatSnapshot {
+    #[serde(with = "chrono_serde")]
+    #[schemars(with = "String")]
+    at: DateTime<Local>,
+    hunger: u8,
+    happiness: u8,
+    energy: u8,
+    health: u8,
+    mood: NybblerMood,
+}
 
-    Ok(save_dir)
+impl StatSnapshot {
+    // Overall wellbeing at the time of the snapshot, scored like Nybbler::wellness
+    fn wellness(&self) -> u8 {
+        average_stat([self.hunger, self.happiness, self.energy, self.health])
+    }
 }
 
-// Delete all Nybbler save files
-fn delete_all_nybblers() -> io::Result<usize> {
-    let save_dir = get_save_directory()?;
+// Which way a Nybbler's overall wellness is heading
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Trend {
+    Rising,
+    Steady,
+    Falling,
+}
 
-    // Make sure the directory exists
-    if !save_dir.exists() {
-        return Ok(0);
+impl Trend {
+    // Compare a wellness score with an earlier one
+    fn between(earlier: u8, now: u8) -> Self {
+        match now.cmp(&earlier) {
+            std::cmp::Ordering::Greater => Trend::Rising,
+            std::cmp::Ordering::Equal => Trend::Steady,
+            std::cmp::Ordering::Less => Trend::Falling,
+        }
     }
 
-    let mut count = 0;
-    for entry in fs::read_dir(save_dir)? {
-        let entry = entry?;
-        let path = entry.path();
+    // Get the arrow shown next to the header
+    fn arrow(self, render: Render) -> &'static str {
+        match self {
+            Trend::Rising => render.pick("↑", "^"),
+            Trend::Steady => render.pick("→", "="),
+            Trend::Falling => render.pick("↓", "v"),
+        }
+    }
 
-        // Only delete JSON files
-        if path.extension().map_or(false, |ext| ext == "json") {
-            fs::remove_file(path)?;
-            count += 1;
+    // Describe the trend for display
+    fn describe(self) -> &'static str {
+        match self {
+            Trend::Rising => "rising",
+            Trend::Steady => "holding steady",
+            Trend::Falling => "falling",
         }
     }
+}
 
-    Ok(count)
+// How well off a new Nybbler is when it hatches
+#[derive(Clone, Copy, PartialEq, Debug, Default, Serialize, Deserialize, ValueEnum)]
+enum StartingProfile {
+    // Half full and half happy, with plenty of energy and health
+    #[default]
+    Standard,
+    // A tougher start for players who want a challenge from day one
+    Scrappy,
+    // A well-fed, cheerful start
+    Pampered,
 }
 
-// Command line arguments structure
-#[derive(Parser)]
-#[command(name = "nybbler")]
-#[command(about = "🎮 Nybbler: The Terminal Virtual Pet 🐙", long_about = None)]
-struct Cli {
-    #[command(subcommand)]
-    command: Option<Commands>,
+impl StartingProfile {
+    // Starting hunger, happiness, energy, and health for each profile
+    fn stats(self) -> [u8; 4] {
+        match self {
+            StartingProfile::Standard => [50, 50, 100, 100],
+            StartingProfile::Scrappy => [30, 30, 70, 80],
+            StartingProfile::Pampered => [80, 80, 100, 100],
+        }
+    }
 }
 
-#[derive(Subcommand)]
-enum Commands {
-    /// Delete all Nybbler pets
-    DeleteAll,
+// Personality traits drawn at random when a Nybbler hatches
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize, JsonSchema)]
+enum Personality {
+    // Gets hungry faster but loves every meal
+    Gluttonous,
+    // Runs out of energy more slowly
+    Energetic,
+    // Gets lonely when you switch to another pet
+    Shy,
+    // Shrugs off some of the harm from neglect
+    Brave,
 }
 
-fn display_stats(nybbler: &Nybbler, term: &Term) -> Result<(), std::io::Error> {
-    term.clear_screen()?;
+impl Personality {
+    // Every trait a Nybbler can have
+    const ALL: [Personality; 4] = [Personality::Gluttonous, Personality::Energetic, Personality::Shy, Personality::Brave];
 
-    // Display fancy header with border
-    let header = format!("✨ {} the Nybbler ✨  Age: {} days 🎂", nybbler.name, nybbler.age);
-    let border = "•*´¨`*•.¸¸.•*´¨`*•.¸¸.•*´¨`*•.¸¸.•*´¨`*•.¸¸.•";
+    // Pick one or two different traits for a new Nybbler
+    fn random<R: Rng>(rng: &mut R) -> Vec<Self> {
+        let count = rng.gen_range(1..=2);
+        Personality::ALL.choose_multiple(rng, count).copied().collect()
+    }
 
-    println!("{}", style(border).cyan());
-    println!("{}", style(header).bold().magenta());
-    println!("{}", style(border).cyan());
+    // How much faster than usual this trait makes a stat fall over time
+    fn decay_multiplier(self, stat: Stat) -> f64 {
+        match (self, stat) {
+            (Personality::Gluttonous, Stat::Hunger) => 1.5,
+            (Personality::Energetic, Stat::Energy) => 0.7,
+            _ => 1.0,
+        }
+    }
+}
 
-    // Display animated mood
-    let mood_text = match nybbler.mood {
-        NybblerMood::Happy => "💖 I'm happy! 💖",
-        NybblerMood::Neutral => "🌱 I'm doing okay. 🌱",
-        NybblerMood::Sad => "💧 I'm feeling sad... 💧",
-        NybblerMood::Sick => "🌡️ I don't feel well... 💊",
-        NybblerMood::Sleeping => "💤 Zzz... 💤",
-        NybblerMood::Excited => "✨ I'm super excited! ✨",
-        NybblerMood::Playful => "🎮 Let's play! 🎮",
-    };
+// Hash bytes with 64-bit FNV-1a, which unlike the standard hasher gives the same
+// answer on every build and platform
+fn fnv1a(bytes: impl IntoIterator<Item = u8>) -> u64 {
+    bytes.into_iter().fold(FNV_OFFSET_BASIS, |hash, byte| (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME))
+}
 
-    println!("{} {}", style(nybbler.mood.to_emoji()).bold(), style(mood_text).italic());
+// How a Nybbler woke up today, fixed for each name and date so nothing is saved
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum DailyMood {
+    // Woke up on the wrong side of the bed, so happiness falls a little faster
+    Grumpy,
+    Ordinary,
+    // In a sunny mood, so happiness falls a little slower
+    Cheerful,
+}
 
-    // Display the pixelated character
-    let character_display = match nybbler.mood {
-        NybblerMood::Sleeping => nybbler.character_type.sleeping(),
-        _ => nybbler.character_type.neutral(),
-    };
-    println!("{}", style(character_display).bold().yellow());
+impl DailyMood {
+    // Today's mood for a pet, the same every time it's asked for on that date
+    fn for_day(name: &str, date: NaiveDate) -> Self {
+        let name = name.trim().to_lowercase();
+        let day = date.num_days_from_ce().to_le_bytes();
+        DailyMood::from_seed(fnv1a(name.bytes().chain(day)))
+    }
 
-    println!();
+    // Roll a mood from a seed: one day in five is grumpy and one is cheerful
+    fn from_seed(seed: u64) -> Self {
+        match StdRng::seed_from_u64(seed).gen_range(0..5) {
+            0 => DailyMood::Grumpy,
+            1 => DailyMood::Cheerful,
+            _ => DailyMood::Ordinary,
+        }
+    }
 
-    // Display stats bars with cute emojis
-    let get_bar_style = |emoji: &str| {
-        ProgressStyle::with_template(&format!("{}  [{{bar:20.green/red}}] {{pos}}/{{len}}", emoji))
-            .unwrap()
-            .progress_chars("█▉▊▋▌▍▎▏ ")
-    };
+    // How the day is going, if it's anything out of the ordinary
+    fn describe(self) -> Option<&'static str> {
+        match self {
+            DailyMood::Grumpy => Some("woke up on the wrong side of the bed"),
+            DailyMood::Ordinary => None,
+            DailyMood::Cheerful => Some("woke up in a sunny mood"),
+        }
+    }
 
-    // Hunger
-    let hunger_bar = ProgressBar::new(100);
-    hunger_bar.set_style(get_bar_style("🍔"));
-    hunger_bar.set_position(nybbler.hunger as u64);
-    println!("{}:", style("Hunger").bold().blue());
-    hunger_bar.tick();
+    // Extra happiness lost per hour, kept small so care still matters most
+    fn extra_happiness_decay(self) -> f64 {
+        match self {
+            DailyMood::Grumpy => 1.0,
+            DailyMood::Ordinary => 0.0,
+            DailyMood::Cheerful => -1.0,
+        }
+    }
+}
 
-    // Happiness
-    let happiness_bar = ProgressBar::new(100);
-    happiness_bar.set_style(get_bar_style("🎈"));
-    happiness_bar.set_position(nybbler.happiness as u64);
-    println!("{}:", style("Happiness").bold().magenta());
-    happiness_bar.tick();
+// How close a Nybbler feels to its owner, built up over weeks of care
+#[derive(Clone, Copy, PartialEq, PartialOrd, Debug)]
+enum BondTier {
+    Stranger,
+    Friend,
+    Companion,
+    Soulmate,
+}
 
-    // Energy
-    let energy_bar = ProgressBar::new(100);
-    energy_bar.set_style(get_bar_style("⚡"));
-    energy_bar.set_position(nybbler.energy as u64);
-    println!("{}:", style("Energy").bold().yellow());
-    energy_bar.tick();
+impl BondTier {
+    // The tier a bond value falls into
+    fn from_bond(bond: u8) -> Self {
+        match bond {
+            80.. => BondTier::Soulmate,
+            50..=79 => BondTier::Companion,
+            20..=49 => BondTier::Friend,
+            _ => BondTier::Stranger,
+        }
+    }
 
-    // Health
-    let health_bar = ProgressBar::new(100);
-    health_bar.set_style(get_bar_style("💖"));
-    health_bar.set_position(nybbler.health as u64);
-    println!("{}:", style("Health").bold().red());
-    health_bar.tick();
+    // Describe the relationship in a sentence like "You and Fluffy are friends"
+    fn describe(self) -> &'static str {
+        match self {
+            BondTier::Stranger => "are still getting to know each other",
+            BondTier::Friend => "are friends",
+            BondTier::Companion => "are close companions",
+            BondTier::Soulmate => "are inseparable",
+        }
+    }
 
-    println!();
-    Ok(())
+    // A Nybbler that trusts its owner frets less while alone, so its stats fall slower
+    fn decay_multiplier(self) -> f64 {
+        match self {
+            BondTier::Stranger => 1.0,
+            BondTier::Friend => 0.95,
+            BondTier::Companion => 0.9,
+            BondTier::Soulmate => 0.85,
+        }
+    }
 }
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // Parse command line arguments
-    let cli = Cli::parse();
+// A signature color for each Nybbler's name so pets look distinct
+#[derive(Clone, Copy, PartialEq, Debug, Default, Serialize, Deserialize, JsonSchema)]
+enum PetColor {
+    // Names were always yellow before pets had their own colors
+    #[default]
+    Yellow,
+    Cyan,
+    Green,
+    Magenta,
+    Blue,
+}
 
-    // Handle commands
-    if let Some(Commands::DeleteAll) = cli.command {
-        match delete_all_nybblers() {
-            Ok(count) => {
-                println!("🗑️ Successfully deleted {} Nybbler pets!", count);
-                println!("🎮 Run the game without arguments to create a new pet.");
-                return Ok(());
-            },
-            Err(e) => {
-                eprintln!("Error deleting Nybblers: {}", e);
-                process::exit(1);
+impl PetColor {
+    // Every color a new Nybbler can get
+    const ALL: [PetColor; 5] = [PetColor::Yellow, PetColor::Cyan, PetColor::Green, PetColor::Magenta, PetColor::Blue];
+
+    // Pick a color for a new Nybbler
+    fn random<R: Rng>(rng: &mut R) -> Self {
+        *PetColor::ALL.choose(rng).unwrap_or(&PetColor::Yellow)
+    }
+
+    // The terminal color used to draw the name
+    fn color(self) -> Color {
+        match self {
+            PetColor::Yellow => Color::Yellow,
+            PetColor::Cyan => Color::Cyan,
+            PetColor::Green => Color::Green,
+            PetColor::Magenta => Color::Magenta,
+            PetColor::Blue => Color::Blue,
+        }
+    }
+}
+
+// Life stages a Nybbler grows through as it ages
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Serialize, Deserialize, JsonSchema)]
+enum LifeStage {
+    Baby,
+    Child,
+    Teen,
+    Adult,
+    Elder,
+}
+
+impl LifeStage {
+    // Determine the life stage for an age in days
+    fn from_age(age: u16) -> Self {
+        match age {
+            0..=2 => LifeStage::Baby,
+            3..=7 => LifeStage::Child,
+            8..=15 => LifeStage::Teen,
+            16..=59 => LifeStage::Adult,
+            _ => LifeStage::Elder,
+        }
+    }
+
+    // Name the stage with its article, as in "grew into an Adult"
+    fn with_article(self) -> &'static str {
+        match self {
+            LifeStage::Baby => "a Baby",
+            LifeStage::Child => "a Child",
+            LifeStage::Teen => "a Teen",
+            LifeStage::Adult => "an Adult",
+            LifeStage::Elder => "an Elder",
+        }
+    }
+
+    // Pad the character art so younger Nybblers look smaller on screen
+    fn frame_art(self, art: &str) -> String {
+        let indent = match self {
+            LifeStage::Baby => 6,
+            LifeStage::Child => 4,
+            LifeStage::Teen => 2,
+            LifeStage::Adult | LifeStage::Elder => 0,
+        };
+        let padding = " ".repeat(indent);
+
+        let mut framed = String::new();
+        let mut previous: Option<&str> = None;
+        for line in art.lines() {
+            // Babies are squashed by dropping repeated rows of their body
+            if self == LifeStage::Baby && previous == Some(line) {
+                continue;
+            }
+            previous = Some(line);
+
+            if !line.is_empty() {
+                framed.push_str(&padding);
             }
+            framed.push_str(line);
+            framed.push('\n');
         }
+        framed
     }
+}
 
-    // Regular game flow
-    let term = Term::stdout();
-    term.clear_screen()?;
+// The Nybbler struct to hold the game state
+#[derive(Serialize, Deserialize, JsonSchema)]
+struct Nybbler {
+    // Saves from before versioning count as version 0
+    #[serde(default)]
+    version: u32,
+    name: String,
+    hunger: u8,
+    happiness: u8,
+    energy: u8,
+    health: u8,
+    // Saves from before bathing existed start out clean
+    #[serde(default = "default_cleanliness")]
+    cleanliness: u8,
+    age: u16,
+    #[serde(with = "chrono_serde")]
+    #[schemars(with = "String")]
+    last_updated: DateTime<Local>,
+    mood: NybblerMood,
+    #[serde(default = "characters::CharacterType::from_entropy")]
+    #[schemars(transform = drop_schema_default)]
+    character_type: characters::CharacterType,
+    #[serde(default)]
+    mood_history: VecDeque<MoodEntry>,
+    #[serde(default)]
+    recent_treats: u8,
+    // When the Nybbler last forgot a treat, or got its first one since, timing when it forgets the next
+    #[serde(default, with = "chrono_serde::option")]
+    #[schemars(with = "Option<String>")]
+    treats_forgotten_at: Option<DateTime<Local>>,
+    #[serde(default)]
+    notes: String,
+    #[serde(default)]
+    snapshots: VecDeque<StatSnapshot>,
+    #[serde(default, with = "chrono_serde::option")]
+    #[schemars(with = "Option<String>")]
+    last_favorite_at: Option<DateTime<Local>>,
+    #[serde(default, with = "chrono_serde::map")]
+    #[schemars(with = "BTreeMap<Action, String>")]
+    cooldowns: BTreeMap<Action, DateTime<Local>>,
+    #[serde(default = "default_alert_threshold")]
+    alert_threshold: u8,
+    #[serde(default)]
+    events: VecDeque<ActionEvent>,
+    #[serde(default, with = "chrono_serde::map")]
+    #[schemars(with = "BTreeMap<LifeStage, String>")]
+    milestones: BTreeMap<LifeStage, DateTime<Local>>,
+    #[serde(default)]
+    announced_milestones: BTreeSet<LifeStage>,
+    #[serde(default)]
+    color: PetColor,
+    #[serde(default, with = "chrono_serde::option")]
+    #[schemars(with = "Option<String>")]
+    last_company_at: Option<DateTime<Local>>,
+    #[serde(default)]
+    hardy: bool,
+    // Older saves hatched before personalities existed and have none
+    #[serde(default)]
+    personality: Vec<Personality>,
+    // At most one pet is the favorite, resumed by default and starred in lists
+    #[serde(default)]
+    is_favorite: bool,
+    // Experience earned from care since reaching the current level
+    #[serde(default)]
+    xp: u32,
+    #[serde(default = "default_level")]
+    level: u16,
+    // Long-term trust, slow to build and slow to lose
+    #[serde(default)]
+    bond: u8,
+    #[serde(default, with = "chrono_serde::option")]
+    #[schemars(with = "Option<String>")]
+    last_bonded_at: Option<DateTime<Local>>,
+    // Fractions of a point each stat has moved but not yet applied, in Stat::ALL order,
+    // so frequent short updates still add up
+    #[serde(default)]
+    stat_carry: [f64; 5],
+}
 
-    // Welcome message with cute ASCII art
-    println!("{}", style("
-     /\\_/\\
-    ( o.o )
-     > ^ <
-  ✨ NYBBLER ✨").bold().yellow());
-    println!("{}", style("Welcome to Terminal Nybbler!").bold().green());
-    println!("{}", style("🌈 Take care of your virtual pet and keep it happy! 🌈").cyan());
-    println!("{}", style("✨ You can create a new pet or load an existing one by name! ✨").italic().magenta());
-    println!();
+// Old saves get a random character type, so the schema shouldn't claim a fixed default
+fn drop_schema_default(schema: &mut schemars::Schema) {
+    schema.remove("default");
+}
 
-    // Ask for a name (or to load an existing Nybbler)
-    let name = dialoguer::Input::<String>::new()
-        .with_prompt("Enter your Nybbler's name (new or existing)")
-        .interact_text()?;
+// Alert threshold for saves from before it was configurable
+fn default_alert_threshold() -> u8 {
+    DEFAULT_ALERT_THRESHOLD
+}
 
-    // Check if a save exists and ask if we should load it
-    let mut nybbler = if Nybbler::save_exists(&name) {
-        let load_save = dialoguer::Confirm::new()
-            .with_prompt(format!("A Nybbler named {} already exists! Would you like to load it?", name))
-            .default(true)
-            .interact()?;
+// Cleanliness for saves from before it was tracked
+fn default_cleanliness() -> u8 {
+    100
+}
 
-        if load_save {
-            match Nybbler::load(&name) {
-                Ok(loaded) => {
-                    println!("{} {} has been loaded! {}", style("🎉").bold(), style(&name).bold().yellow(), style("🎉").bold());
-                    println!("{} Time has passed since you last played... {}", style("⏰").bold(), style("⏰").bold());
-                    thread::sleep(Duration::from_millis(1500));
-                    loaded
-                },
-                Err(e) => {
-                    println!("Error loading save: {}", e);
-                    println!("Creating a new Nybbler instead...");
-                    thread::sleep(Duration::from_millis(1500));
-                    Nybbler::new(name)
-                }
+// Saves from before leveling start at the first level
+fn default_level() -> u16 {
+    1
+}
+
+// Helper module to serialize/deserialize chrono::DateTime
+// Saves keep the offset they were written with, but every timestamp is
+// converted to the current local time zone on load, so durations stay exact
+// and anything shown to the player is in the zone they're in now
+mod chrono_serde {
+    use chrono::{DateTime, Local};
+    use serde::{self, Deserialize, Deserializer, Serializer};
+
+    // Parse an RFC 3339 timestamp into the current local time zone
+    pub fn parse<E: serde::de::Error>(s: &str) -> Result<DateTime<Local>, E> {
+        DateTime::parse_from_rfc3339(s)
+            .map(|dt| dt.with_timezone(&Local))
+            .map_err(E::custom)
+    }
+
+    pub fn serialize<S>(date: &DateTime<Local>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let s = date.to_rfc3339();
+        serializer.serialize_str(&s)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<DateTime<Local>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        parse(&s)
+    }
+
+    // The same format for optional timestamps
+    pub mod option {
+        use chrono::{DateTime, Local};
+        use serde::{Deserialize, Deserializer, Serializer};
+
+        pub fn serialize<S>(date: &Option<DateTime<Local>>, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            match date {
+                Some(date) => super::serialize(date, serializer),
+                None => serializer.serialize_none(),
             }
-        } else {
-            println!("Creating a new Nybbler named {}...", name);
-            Nybbler::new(name)
         }
-    } else {
-        // Create new Nybbler
-        Nybbler::new(name)
-    };
 
-    // Main game loop
-    loop {
-        // Update nybbler state
-        nybbler.update();
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<DateTime<Local>>, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            let s = Option::<String>::deserialize(deserializer)?;
+            s.map(|s| super::parse(&s)).transpose()
+        }
+    }
 
-        // Check if nybbler is alive
-        if !nybbler.is_alive() {
-            term.clear_screen()?;
-            println!("{}", style("
-      .======.
-      | RIP |
-      |      |
-      |      |
-      |      |
-      '======'").bold());
-            println!("{}", style("💔 Oh no! Your Nybbler has passed away! 💔").bold().red());
-            println!("🌈 {} lived for {} wonderful days with you. 🌈", nybbler.name, nybbler.age);
-            println!("🌟 Thank you for taking care of your Nybbler! 🌟");
-            break;
+    // The same format for maps of timestamps
+    pub mod map {
+        use std::collections::BTreeMap;
+
+        use chrono::{DateTime, Local};
+        use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+        // Keys are kept sorted so saves of the same state are byte-identical
+        pub fn serialize<K, S>(map: &BTreeMap<K, DateTime<Local>>, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            K: Serialize + Ord,
+            S: Serializer,
+        {
+            let strings: BTreeMap<&K, String> = map.iter().map(|(k, date)| (k, date.to_rfc3339())).collect();
+            strings.serialize(serializer)
         }
 
-        // Display stats
-        display_stats(&nybbler, &term)?;
+        pub fn deserialize<'de, K, D>(deserializer: D) -> Result<BTreeMap<K, DateTime<Local>>, D::Error>
+        where
+            K: Deserialize<'de> + Ord,
+            D: Deserializer<'de>,
+        {
+            BTreeMap::<K, String>::deserialize(deserializer)?
+                .into_iter()
+                .map(|(k, s)| super::parse(&s).map(|dt| (k, dt)))
+                .collect()
+        }
+    }
+}
 
-        // Show available actions with cute emojis
-        let options = vec!["🍔 Feed", "🎮 Play", "💤 Sleep", "💊 Heal", "👋 Exit"];
-        let selection = Select::with_theme(&ColorfulTheme::default())
-            .with_prompt("✨ What would you like to do? ✨")
-            .items(&options)
-            .default(0)
-            .interact_on(&term)?;
+impl Nybbler {
+    // Create a new Nybbler starting with the stats of the given profile
+    fn new<R: Rng>(name: String, profile: StartingProfile, rng: &mut R) -> Self {
+        let [hunger, happiness, energy, health] = profile.stats();
+        Nybbler {
+            version: SAVE_VERSION,
+            name,
+            hunger,
+            happiness,
+            energy,
+            health,
+            cleanliness: 100,
+            age: 0,
+            last_updated: Local::now(),
+            mood: NybblerMood::Happy,
+            character_type: characters::CharacterType::random(rng),
+            mood_history: VecDeque::new(),
+            recent_treats: 0,
+            treats_forgotten_at: None,
+            notes: String::new(),
+            snapshots: VecDeque::new(),
+            last_favorite_at: Some(Local::now()),
+            cooldowns: BTreeMap::new(),
+            alert_threshold: DEFAULT_ALERT_THRESHOLD,
+            events: VecDeque::new(),
+            milestones: BTreeMap::new(),
+            announced_milestones: BTreeSet::new(),
+            color: PetColor::random(rng),
+            last_company_at: None,
+            hardy: false,
+            personality: Personality::random(rng),
+            is_favorite: false,
+            xp: 0,
+            level: 1,
+            bond: 0,
+            last_bonded_at: None,
+            stat_carry: [0.0; 5],
+        }
+    }
 
-        // Process selection with cute responses
-        match selection {
-            0 => {
-                nybbler.feed();
-                println!("{} You fed {} a delicious meal! 🍔 Yum yum! {}", style("🎉").bold(), style(&nybbler.name).bold().yellow(), style("🎉").bold());
-                for _ in 0..3 {
-                    thread::sleep(Duration::from_millis(300));
-                    term.clear_last_lines(1)?;
-                    println!("{} Nom nom nom... {} is eating! {}", style("🍽️").bold(), style(&nybbler.name).bold().yellow(), style("🍽️").bold());
-                    println!("{}", style(nybbler.character_type.eating()).bold().yellow());
-                    thread::sleep(Duration::from_millis(300));
-                    term.clear_last_lines(2)?;
-                    println!("{} Yummy! That was delicious! {}", style("😋").bold(), style("😋").bold());
-                    println!("{}", style(nybbler.character_type.neutral()).bold().yellow());
-                }
-            },
-            1 => {
-                nybbler.play();
-                println!("{} You played with {}! So much fun! {}", style("🎮").bold(), style(&nybbler.name).bold().yellow(), style("🎮").bold());
-                for _ in 0..3 {
-                    thread::sleep(Duration::from_millis(300));
-                    term.clear_last_lines(1)?;
-                    println!("{} Wheee! {} is having fun! {}", style("🎯").bold(), style(&nybbler.name).bold().yellow(), style("🎯").bold());
-                    println!("{}", style(nybbler.character_type.playing()).bold().yellow());
-                    thread::sleep(Duration::from_millis(300));
-                    term.clear_last_lines(2)?;
-                    println!("{} Bouncing around with joy! {}", style("🏀").bold(), style("🏀").bold());
-                    println!("{}", style(nybbler.character_type.neutral()).bold().yellow());
-                }
-            },
-            2 => {
-                nybbler.sleep();
-                println!("{} {} took a nap and feels refreshed! {}", style("💤").bold(), style(&nybbler.name).bold().yellow(), style("💤").bold());
-                for _ in 0..3 {
-                    thread::sleep(Duration::from_millis(400));
-                    term.clear_last_lines(1)?;
-                    println!("{} Zzz... {} is sleeping soundly... {}", style("😴").bold(), style(&nybbler.name).bold().yellow(), style("😴").bold());
-                    println!("{}", style(nybbler.character_type.sleeping()).bold().yellow());
-                    thread::sleep(Duration::from_millis(400));
-                    term.clear_last_lines(2)?;
-                    println!("{} Dreaming of treats and toys... {}", style("💭").bold(), style("💭").bold());
-                    println!("{}", style(nybbler.character_type.sleeping()).bold().yellow());
-                }
-            },
-            3 => {
-                nybbler.heal();
-                println!("{} You gave {} medicine and they're feeling better! {}", style("💊").bold(), style(&nybbler.name).bold().yellow(), style("💊").bold());
-                for _ in 0..3 {
-                    thread::sleep(Duration::from_millis(300));
-                    term.clear_last_lines(1)?;
-                    println!("{} {} is recovering... {}", style("🌡️").bold(), style(&nybbler.name).bold().yellow(), style("🌡️").bold());
-                    println!("{}", style(nybbler.character_type.healing()).bold().yellow());
-                    thread::sleep(Duration::from_millis(300));
-                    term.clear_last_lines(2)?;
-                    println!("{} All better now! Healthy and strong! {}", style("💪").bold(), style("💪").bold());
-                    println!("{}", style(nybbler.character_type.neutral()).bold().yellow());
-                }
-            },
-            4 => {
-                if confirm_exit()? {
-                    // Save the nybbler before exiting
-                    match nybbler.save() {
-                        Ok(_) => {
-                            println!("{} {} has been saved successfully! {}", style("💾").bold(), style(&nybbler.name).bold().yellow(), style("💾").bold());
-                            thread::sleep(Duration::from_millis(1000));
-                        },
-                        Err(e) => {
-                            println!("Error saving nybbler: {}", e);
-                            thread::sleep(Duration::from_millis(1000));
-                        }
-                    }
-                    term.clear_screen()?;
-                    println!("{}", style("
-      /\\_/\\
-     ( ^ω^ )
-     / >👋< \\
-    Goodbye!").bold().yellow());
-                    println!("{}", style("👋 Goodbye! See you soon! 👋").bold().green());
-                    println!("🌈 {} will be waiting for your return! 🌈", nybbler.name);
-                    break;
-                }
-            },
-            _ => unreachable!(),
+    // Save the Nybbler state to a file
+    fn save(&self) -> Result<(), SaveError> {
+        let save_dir = get_save_directory()?;
+        self.save_path(&save_dir.join(save_file_name(&self.name)))
+    }
+
+    // Save the Nybbler state to a specific file path
+    fn save_path(&self, path: &Path) -> Result<(), SaveError> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(io::Error::other)?;
+
+        // Write beside the save and rename it into place, so a crash
+        // mid-write leaves the previous save whole instead of truncated
+        let temp_path = path.with_extension("json.tmp");
+        fs::write(&temp_path, json)?;
+        Ok(fs::rename(&temp_path, path)?)
+    }
+
+    // Load a Nybbler from a file
+    fn load(name: &str) -> Result<Self, SaveError> {
+        validate_name(name).map_err(SaveError::InvalidName)?;
+        let save_dir = get_save_directory()?;
+        let save_path = save_dir.join(save_file_name(name));
+
+        Self::load_path(&save_path)
+    }
+
+    // Load a Nybbler from a specific save file path
+    fn load_path(path: &Path) -> Result<Self, SaveError> {
+        let data = fs::read_to_string(path).map_err(|e| match e.kind() {
+            ErrorKind::NotFound => SaveError::NotFound(path.to_path_buf()),
+            _ => SaveError::Io(e),
+        })?;
+        let (nybbler, clamped) = Self::from_json(&data).map_err(SaveError::Parse)?;
+
+        // Hand-edited or corrupted saves may hold out-of-range stats
+        for stat in clamped {
+            eprintln!("Warning: {} in {} was out of range and has been clamped to 100", stat, path.display());
         }
 
-        // Short delay to see the action result
-        thread::sleep(Duration::from_millis(1000));
+        Ok(nybbler)
     }
 
-    Ok(())
-}
+    // Load a Nybbler to change and save, locking it first so no one else saves over it
+    fn load_locked(path: &Path) -> Result<(Self, PetLock), SaveError> {
+        // A missing pet shouldn't leave a lock file behind
+        if !path.exists() {
+            return Err(SaveError::NotFound(path.to_path_buf()));
+        }
+        let lock = PetLock::acquire(path)?;
+        Ok((Self::load_path(path)?, lock))
+    }
 
-fn confirm_exit() -> Result<bool, std::io::Error> {
-    let confirm = dialoguer::Confirm::new()
-        .with_prompt("🥺 Are you really sure you want to leave? Your Nybbler will miss you! 🥺")
-        .default(false)
-        .interact()?;
+    // Load a Nybbler by name for a read-only command, tolerating a save that's mid-write
+    fn read(name: &str) -> Result<Self, SaveError> {
+        validate_name(name).map_err(SaveError::InvalidName)?;
+        let save_dir = get_save_directory()?;
+        Self::read_path(&save_dir.join(save_file_name(name)))
+    }
 
-    Ok(confirm)
-}
\ No newline at end of file
+    // Load a save another session may be writing at the same moment,
+    // giving a save that fails to parse one more chance after a short pause
+    fn read_path(path: &Path) -> Result<Self, SaveError> {
+        match Self::load_path(path) {
+            Err(SaveError::Parse(_)) => {
+                thread::sleep(Duration::from_millis(READ_RETRY_MILLIS));
+                Self::load_path(path)
+            },
+            result => result,
+        }
+    }
+
+    // Read a Nybbler from save JSON, migrating an older format and clamping
+    // out-of-range stats, whose names are returned alongside it
+    fn from_json(data: &str) -> Result<(Self, Vec<&'static str>), serde_json::Error> {
+        let mut nybbler: Nybbler = serde_json::from_str(data)?;
+        nybbler.migrate()?;
+        let clamped = nybbler.clamp_stats();
+        Ok((nybbler, clamped))
+    }
+
+    // Bring a save written in an older format up to date, one version at a time
+    // Fields with serde defaults are already filled in; this fixes up what a default can't
+    // A save from a newer version is refused, since saving it again would drop what it added
+    fn migrate(&mut self) -> Result<(), serde_json::Error> {
+        if self.version > SAVE_VERSION {
+            return Err(serde::de::Error::custom(format!(
+                "it was written by a newer version of nybbler (save format {}, this version reads up to {})",
+                self.version, SAVE_VERSION
+            )));
+        }
+        if self.version < 1 {
+            // Levels start at 1, and a pet cared for before bonds existed has earned some trust
+            self.level = self.level.max(1);
+            if self.bond == 0 {
+                let care_days: BTreeSet<NaiveDate> = self.events.iter().map(|event| event.at.date_naive()).collect();
+                self.bond = care_days.len().min(100) as u8;
+            }
+            self.version = 1;
+        }
+        Ok(())
+    }
+
+    // Clamp every stat into 0..=100, returning the names of the stats that were out of range
+    fn clamp_stats(&mut self) -> Vec<&'static str> {
+        let mut clamped = Vec::new();
+        for (name, stat) in [
+            ("hunger", &mut self.hunger),
+            ("happiness", &mut self.happiness),
+            ("energy", &mut self.energy),
+            ("health", &mut self.health),
+            ("cleanliness", &mut self.cleanliness),
+            ("bond", &mut self.bond),
+        ] {
+            if *stat > 100 {
+                *stat = 100;
+                clamped.push(name);
+            }
+        }
+        clamped
+    }
+
+    // Check if a save file exists for a Nybbler
+    fn save_exists(name: &str) -> bool {
+        if let Ok(save_dir) = resolve_save_directory() {
+            let save_path = save_dir.join(save_file_name(name));
+            save_path.exists()
+        } else {
+            false
+        }
+    }
+
+    // Update the Nybbler's stats based on elapsed time
+    fn update(&mut self, config: &Config) {
+        self.update_at(Local::now(), config);
+    }
+
+    // Update the Nybbler's stats as if the current time were `now`
+    // A gap long enough to count as an absence gets the configured grace period
+    fn update_at(&mut self, now: DateTime<Local>, config: &Config) {
+        let away = now.signed_duration_since(self.last_updated).num_minutes() >= ABSENCE_MIN_MINUTES;
+        let grace_hours = if away { config.grace_hours.max(0.0) } else { 0.0 };
+        self.update_with_grace(now, config, grace_hours);
+    }
+
+    // Update the stats as if the current time were `now`, letting the first
+    // `grace_hours` of the gap pass without any decay
+    fn update_with_grace(&mut self, now: DateTime<Local>, config: &Config, grace_hours: f64) {
+        let diff = now.signed_duration_since(self.last_updated);
+
+        // Skip tiny intervals so rapid menu navigation doesn't distort stats;
+        // last_updated stays put so the skipped time still accrues
+        if diff.num_seconds() < MIN_UPDATE_INTERVAL_SECS {
+            return;
+        }
+
+        let hours_passed = diff.num_seconds() as f64 / 3600.0;
+        let was_alive = self.is_alive();
+
+        // Decrease stats based on time, shaped by the configured decay curve
+        // and slowed down by adulthood, personality, and bond;
+        // the whole gap counts, so long absences take their full toll
+        let curve = config.decay_curve;
+        let decay_hours = curve.decay_hours(self.last_updated, now, grace_hours);
+        let hardiness = if self.hardy { ADULT_DECAY_MULTIPLIER } else { 1.0 };
+        let rate = |stat: Stat, per_hour: f64| {
+            per_hour
+                * hardiness
+                * self.personality_decay(stat)
+                * self.bond_tier().decay_multiplier()
+        };
+        let hunger_decrease = curve.decay(rate(Stat::Hunger, config.hunger_decay_per_hour.max(0.0)), decay_hours, self.hunger);
+        let daily_mood = DailyMood::for_day(&self.name, now.date_naive());
+        let happiness_per_hour = (config.happiness_decay_per_hour + daily_mood.extra_happiness_decay()).max(0.0);
+        let happiness_decrease = curve.decay(rate(Stat::Happiness, happiness_per_hour), decay_hours, self.happiness);
+        let energy_decrease = curve.decay(rate(Stat::Energy, config.energy_decay_per_hour.max(0.0)), decay_hours, self.energy);
+        let cleanliness_decrease = curve.decay(rate(Stat::Cleanliness, CLEANLINESS_DECAY_PER_HOUR), decay_hours, self.cleanliness);
+
+        // Apply decreases, carrying any fraction of a point over to the next update
+        self.shift_stat(Stat::Hunger, -hunger_decrease);
+        self.shift_stat(Stat::Happiness, -happiness_decrease);
+        self.shift_stat(Stat::Energy, -energy_decrease);
+        self.shift_stat(Stat::Cleanliness, -cleanliness_decrease);
+
+        // Going too long without its favorite activity makes the Nybbler sulk;
+        // the clock restarts so the penalty comes at most once per neglect period,
+        // and like every other loss it waits out the grace period
+        let last_favorite = *self.last_favorite_at.get_or_insert(now);
+        if decay_hours > 0.0 && now.signed_duration_since(last_favorite).num_hours() >= FAVORITE_NEGLECT_HOURS {
+            self.happiness = self.happiness.saturating_sub(FAVORITE_NEGLECT_PENALTY);
+            self.last_favorite_at = Some(now);
+        }
+
+        // Treats wear off gradually, restoring their effect; the clock only moves
+        // on by whole treats so frequent short updates still add up
+        if self.recent_treats == 0 {
+            self.treats_forgotten_at = None;
+        } else {
+            let since = *self.treats_forgotten_at.get_or_insert(self.last_updated);
+            let period = (TREAT_RECOVERY_HOURS * 3600.0) as i64;
+            let forgotten = (now.signed_duration_since(since).num_seconds() / period).max(0);
+            self.recent_treats = self.recent_treats.saturating_sub(u8::try_from(forgotten).unwrap_or(u8::MAX));
+            self.treats_forgotten_at = Some(since + chrono::Duration::seconds(forgotten * period));
+        }
+
+        // Trust fades only over whole days alone
+        let days_alone = (hours_passed / 24.0).min(100.0) as u8;
+        self.bond = self.bond.saturating_sub(days_alone.saturating_mul(BOND_LOSS_PER_DAY));
+
+        // Update age (1 day every 24 real hours), saturating instead of wrapping
+        self.age = self.age.saturating_add((hours_passed / 24.0) as u16);
+
+        self.record_milestone(now);
+
+        // Update health based on hunger, happiness, and cleanliness, except during a grace period
+        if decay_hours > 0.0 && (self.hunger < 20 || self.happiness < 20 || self.cleanliness < 20) {
+            let loss = if self.has_trait(Personality::Brave) { BRAVE_NEGLECT_HEALTH_LOSS } else { NEGLECT_HEALTH_LOSS };
+            self.health = self.health.saturating_sub(loss);
+        }
+
+        // Slowly recover health while the Nybbler is well cared for
+        if self.hunger > HEALTHY_STAT_THRESHOLD
+            && self.happiness > HEALTHY_STAT_THRESHOLD
+            && self.energy > HEALTHY_STAT_THRESHOLD
+        {
+            self.shift_stat(Stat::Health, HEALTH_RECOVERY_PER_HOUR * hours_passed);
+        }
+
+        // Update mood based on stats
+        self.update_mood_at(now);
+
+        // Remember how the Nybbler was doing for the timelapse
+        self.record_snapshot(now);
+
+        eventlog::record(&format!(
+            "{}: updated after {:.2}h (hunger {}, happiness {}, energy {}, health {}, age {})",
+            self.name, hours_passed, self.hunger, self.happiness, self.energy, self.health, self.age
+        ));
+        if was_alive && !self.is_alive() {
+            eventlog::record(&format!("{}: died at {} days old", self.name, self.age));
+        }
+
+        // Update timestamp
+        self.last_updated = now;
+    }
+
+    // Remember when the Nybbler first grew into each life stage
+    fn record_milestone(&mut self, now: DateTime<Local>) {
+        let stage = self.life_stage();
+        if stage != LifeStage::Baby {
+            self.milestones.entry(stage).or_insert(now);
+        }
+
+        // Surviving to adulthood is rewarded with slower decay for good
+        if stage >= LifeStage::Adult {
+            self.hardy = true;
+        }
+    }
+
+    // Life stages reached since the last call, each returned only once
+    fn take_milestone_announcements(&mut self) -> Vec<LifeStage> {
+        let new: Vec<LifeStage> = self
+            .milestones
+            .keys()
+            .filter(|stage| !self.announced_milestones.contains(stage))
+            .copied()
+            .collect();
+        self.announced_milestones.extend(&new);
+        new
+    }
+
+    // Record the current stats in the capped snapshot history
+    fn record_snapshot(&mut self, now: DateTime<Local>) {
+        let snapshot = StatSnapshot {
+            at: now,
+            hunger: self.hunger,
+            happiness: self.happiness,
+            energy: self.energy,
+            health: self.health,
+            mood: self.mood,
+        };
+
+        // A burst of actions keeps only its latest state, but the snapshot keeps
+        // the time it was first taken so an active session still gets new ones
+        if let Some(last) = self.snapshots.back_mut() {
+            if (now - last.at).num_seconds() < SNAPSHOT_MIN_INTERVAL_SECS {
+                *last = StatSnapshot { at: last.at, ..snapshot };
+                return;
+            }
+        }
+        if self.snapshots.len() == SNAPSHOT_HISTORY_LEN {
+            self.snapshots.pop_front();
+        }
+        self.snapshots.push_back(snapshot);
+    }
+
+    // Settle the mood after an action and snapshot the result for the timelapse
+    fn finish_action(&mut self) {
+        let now = Local::now();
+        self.gain_xp(XP_PER_ACTION);
+        if self.last_bonded_at.is_none_or(|at| (now - at).num_hours() >= BOND_GAIN_HOURS) {
+            self.build_bond(BOND_CARE_GAIN, now);
+        }
+        self.update_mood_at(now);
+        self.record_snapshot(now);
+    }
+
+    // Grow the bond, remembering when so steady care is what counts
+    fn build_bond(&mut self, gain: u8, now: DateTime<Local>) {
+        self.bond = self.bond.saturating_add(gain).min(100);
+        self.last_bonded_at = Some(now);
+    }
+
+    // How close the Nybbler feels to its owner
+    fn bond_tier(&self) -> BondTier {
+        BondTier::from_bond(self.bond)
+    }
+
+    // Experience needed to go from the current level to the next
+    fn xp_to_next_level(&self) -> u32 {
+        XP_PER_LEVEL * u32::from(self.level.max(1))
+    }
+
+    // Add experience, moving up as many levels as it fills
+    fn gain_xp(&mut self, xp: u32) {
+        self.xp = self.xp.saturating_add(xp);
+        while self.xp >= self.xp_to_next_level() && self.level < u16::MAX {
+            self.xp -= self.xp_to_next_level();
+            self.level += 1;
+        }
+    }
+
+    // How many percent more each action helps at the current level
+    fn level_bonus_percent(&self) -> u16 {
+        self.level.saturating_sub(1).saturating_mul(LEVEL_BONUS_PERCENT).min(MAX_LEVEL_BONUS_PERCENT)
+    }
+
+    // Update the Nybbler's mood based on its stats
+    fn update_mood(&mut self) {
+        self.update_mood_at(Local::now());
+    }
+
+    // Update the mood as if the current time were `now`, recording any change
+    fn update_mood_at(&mut self, now: DateTime<Local>) {
+        let mut mood = if self.health < 30 {
+            NybblerMood::Sick
+        } else if self.energy < 20 {
+            NybblerMood::Sleeping
+        } else if self.hunger < 30 || self.happiness < 30 {
+            NybblerMood::Sad
+        } else if self.hunger > 70 && self.happiness > 70 && self.energy > 70 {
+            NybblerMood::Excited
+        } else if self.hunger > 70 && self.happiness > 70 {
+            NybblerMood::Happy
+        } else if self.happiness > 80 {
+            NybblerMood::Playful
+        } else {
+            NybblerMood::Neutral
+        };
+
+        // Sadness that drags on turns into depression
+        if mood == NybblerMood::Sad && self.hours_in_low_mood(now) >= DEPRESSION_HOURS {
+            mood = NybblerMood::Depressed;
+        }
+
+        // Depression lingers until the Nybbler is properly cared for again
+        if self.mood == NybblerMood::Depressed
+            && mood != NybblerMood::Sick
+            && (self.hunger < DEPRESSION_RECOVERY || self.happiness < DEPRESSION_RECOVERY)
+        {
+            mood = NybblerMood::Depressed;
+        }
+
+        self.set_mood(mood, now);
+    }
+
+    // Change the mood, recording it in the history if it differs
+    fn set_mood(&mut self, mood: NybblerMood, now: DateTime<Local>) {
+        if mood != self.mood {
+            eventlog::record(&format!("{}: mood {:?} -> {:?}", self.name, self.mood, mood));
+            if self.mood_history.len() == MOOD_HISTORY_LEN {
+                self.mood_history.pop_front();
+            }
+            self.mood_history.push_back(MoodEntry { at: now, mood });
+        }
+        self.mood = mood;
+    }
+
+    // How many hours the Nybbler has been continuously sad, sick, or depressed
+    fn hours_in_low_mood(&self, now: DateTime<Local>) -> f64 {
+        let since = self
+            .mood_history
+            .iter()
+            .rev()
+            .take_while(|entry| entry.mood.is_low())
+            .last()
+            .map(|entry| entry.at);
+
+        match since {
+            Some(since) if self.mood.is_low() => now.signed_duration_since(since).num_seconds() as f64 / 3600.0,
+            _ => 0.0,
+        }
+    }
+
+    // Feed the Nybbler
+    fn feed(&mut self) {
+        FEED_EFFECT.apply(self);
+        if self.has_trait(Personality::Gluttonous) {
+            self.happiness = self.happiness.saturating_add(GLUTTON_FEED_HAPPINESS).min(100);
+        }
+        self.enjoy_activity(Activity::Eat);
+        self.finish_action();
+    }
+
+    // Give the Nybbler a treat, returning the happiness gained and whether it caused a sugar crash
+    fn treat(&mut self) -> (u8, bool) {
+        let gained = TREAT_HAPPINESS.saturating_sub(self.recent_treats.saturating_mul(TREAT_FALLOFF));
+        self.happiness = self.happiness.saturating_add(gained).min(100);
+
+        // Too many treats in a row make the Nybbler feel unwell
+        let crashed = self.recent_treats >= SUGAR_CRASH_TREATS;
+        if crashed {
+            self.health = self.health.saturating_sub(SUGAR_CRASH_HEALTH);
+        }
+
+        if self.recent_treats == 0 {
+            self.treats_forgotten_at = Some(Local::now());
+        }
+        self.recent_treats = self.recent_treats.saturating_add(1);
+        self.finish_action();
+        (gained, crashed)
+    }
+
+    // Play with the Nybbler
+    fn play(&mut self, toy: Toy) {
+        toy.effect().apply(self);
+        self.enjoy_activity(Activity::Play);
+        self.finish_action();
+    }
+
+    // Put the Nybbler to sleep
+    fn sleep(&mut self) {
+        // A hungry Nybbler sleeps poorly, so only part of its missing energy comes back
+        let quality = SLEEP_MIN_QUALITY + (1.0 - SLEEP_MIN_QUALITY) * f64::from(self.hunger.min(100)) / 100.0;
+        let missing = 100 - self.energy.min(100);
+        self.energy = self.energy.min(100) + (f64::from(missing) * quality).round() as u8;
+        SLEEP_EFFECT.apply(self);
+        self.enjoy_activity(Activity::Sleep);
+        self.finish_action();
+    }
+
+    // Time left before an action can be performed again, if it's cooling down
+    fn cooldown_remaining(&self, action: Action, now: DateTime<Local>) -> Option<chrono::Duration> {
+        let last = self.cooldowns.get(&action)?;
+        let remaining = *last + action.cooldown()? - now;
+        (remaining > chrono::Duration::zero()).then_some(remaining)
+    }
+
+    // Skip the clock ahead by whole days, as if the player had been away that long
+    fn fast_forward(&mut self, days: u32, config: &Config) {
+        self.fast_forward_at(days, Local::now(), config);
+    }
+
+    // Skip the clock ahead by whole days as if the current time were `now`
+    fn fast_forward_at(&mut self, days: u32, now: DateTime<Local>, config: &Config) {
+        self.update_at(now, config);
+
+        // Backdate the last update and catch up in one go, like a real absence;
+        // skipping ahead is deliberate neglect, so there's no grace period
+        self.last_updated = now - chrono::Duration::days(i64::from(days));
+        self.update_with_grace(now, config, 0.0);
+    }
+
+    // Cheer up from another pet's company, at most once per cooldown
+    fn enjoy_company_at(&mut self, now: DateTime<Local>) -> bool {
+        let recently = self
+            .last_company_at
+            .is_some_and(|last| now.signed_duration_since(last).num_hours() < COMPANY_COOLDOWN_HOURS);
+        if recently {
+            return false;
+        }
+
+        self.happiness = self.happiness.saturating_add(COMPANY_HAPPINESS).min(100);
+        self.last_company_at = Some(now);
+        true
+    }
+
+    // A shy Nybbler gets lonely when you switch away, returning whether it minded
+    fn left_behind(&mut self) -> bool {
+        if !self.has_trait(Personality::Shy) {
+            return false;
+        }
+        self.happiness = self.happiness.saturating_sub(SHY_LEFT_BEHIND_HAPPINESS);
+        self.update_mood();
+        true
+    }
+
+    // This Nybbler's personality traits
+    fn traits(&self) -> &[Personality] {
+        &self.personality
+    }
+
+    // Whether this Nybbler has a given personality trait
+    fn has_trait(&self, personality: Personality) -> bool {
+        self.traits().contains(&personality)
+    }
+
+    // How much faster than usual this Nybbler's personality makes a stat fall
+    fn personality_decay(&self, stat: Stat) -> f64 {
+        self.traits().iter().map(|personality| personality.decay_multiplier(stat)).product()
+    }
+
+    // Days in a row, ending today or yesterday, with at least one recorded act of care
+    fn care_streak(&self, today: NaiveDate) -> u32 {
+        let days: BTreeSet<NaiveDate> = self.events.iter().map(|event| event.at.date_naive()).collect();
+        let Some(mut day) = [today, today - chrono::Days::new(1)].into_iter().find(|day| days.contains(day)) else {
+            return 0;
+        };
+        let mut streak = 0;
+        while days.contains(&day) {
+            streak += 1;
+            day = day - chrono::Days::new(1);
+        }
+        streak
+    }
+
+    // Whether this Elder has been looked after so well that it can move on happily
+    fn can_graduate(&self, now: DateTime<Local>) -> bool {
+        let since = now - chrono::Duration::days(i64::from(GRADUATION_STREAK_DAYS));
+        let mut recent = self.snapshots.iter().filter(|snap| snap.at >= since).peekable();
+        self.is_alive()
+            && self.life_stage() == LifeStage::Elder
+            && self.care_streak(now.date_naive()) >= GRADUATION_STREAK_DAYS
+            && recent.peek().is_some()
+            && recent.all(|snap| snap.wellness() >= GRADUATION_WELLNESS)
+    }
+
+    // Check every precondition for an action, explaining why it can't happen
+    fn try_action(&self, action: Action) -> Result<(), ActionError> {
+        self.try_action_at(action, Local::now())
+    }
+
+    // Check an action's preconditions as if the current time were `now`
+    fn try_action_at(&self, action: Action, now: DateTime<Local>) -> Result<(), ActionError> {
+        if let Some(remaining) = self.cooldown_remaining(action, now) {
+            return Err(ActionError::CoolingDown(remaining));
+        }
+        match action {
+            Action::Play if self.energy < PLAY_MIN_ENERGY => Err(ActionError::TooTired),
+            Action::Comfort if !self.needs_comfort() => Err(ActionError::NotNeeded),
+            _ => Ok(()),
+        }
+    }
+
+    // Remember when an action was performed for cooldowns and the diary
+    fn record(&mut self, action: Action) {
+        let now = Local::now();
+        if action.cooldown().is_some() {
+            self.cooldowns.insert(action, now);
+        }
+        if action.past_tense().is_some() {
+            if self.events.len() == EVENT_HISTORY_LEN {
+                self.events.pop_front();
+            }
+            self.events.push_back(ActionEvent { at: now, action });
+        }
+    }
+
+    // The activity this Nybbler loves most at its current life stage
+    fn favorite_activity(&self) -> Activity {
+        use characters::CharacterType;
+
+        match (self.character_type, self.life_stage()) {
+            (CharacterType::Robo, LifeStage::Baby) => Activity::Eat,
+            (_, LifeStage::Baby) => Activity::Sleep,
+            (CharacterType::Blob, LifeStage::Child | LifeStage::Teen) => Activity::Eat,
+            (CharacterType::Blob, LifeStage::Adult) => Activity::Sleep,
+            (CharacterType::Square | CharacterType::Robo, LifeStage::Child | LifeStage::Teen) => Activity::Play,
+            (CharacterType::Square | CharacterType::Cat, LifeStage::Adult) => Activity::Eat,
+            (CharacterType::Robo, LifeStage::Adult) => Activity::Sleep,
+            (CharacterType::Ghost | CharacterType::Cat, LifeStage::Child) => Activity::Play,
+            (CharacterType::Ghost | CharacterType::Cat, LifeStage::Teen) => Activity::Sleep,
+            (CharacterType::Ghost, LifeStage::Adult) => Activity::Play,
+            // Elders of every kind mostly want a good nap
+            (_, LifeStage::Elder) => Activity::Sleep,
+        }
+    }
+
+    // Give a happiness bonus when the Nybbler gets to do its favorite thing
+    fn enjoy_activity(&mut self, activity: Activity) {
+        if activity == self.favorite_activity() {
+            self.happiness = self.happiness.saturating_add(FAVORITE_ACTIVITY_BONUS).min(100);
+            self.last_favorite_at = Some(Local::now());
+        }
+    }
+
+    // Comfort the Nybbler, returning whether its mood lifted right away
+    fn comfort<R: Rng>(&mut self, rng: &mut R) -> bool {
+        COMFORT_EFFECT.apply(self);
+        // Comfort deepens the bond more than routine care, which then adds nothing more
+        self.build_bond(BOND_COMFORT_GAIN, Local::now());
+        self.finish_action();
+
+        // A hug can lift a low mood before the stats have caught up
+        let lifted = self.needs_comfort() && rng.gen_bool(COMFORT_LIFT_CHANCE);
+        if lifted {
+            let now = Local::now();
+            self.set_mood(NybblerMood::Neutral, now);
+            self.record_snapshot(now);
+        }
+        lifted
+    }
+
+    // Whether the Nybbler is feeling sad or sick enough to want comfort
+    fn needs_comfort(&self) -> bool {
+        matches!(self.mood, NybblerMood::Sad | NybblerMood::Sick)
+    }
+
+    // The actions offered in the menu right now
+    fn available_actions(&self, config: &Config) -> Vec<Action> {
+        config
+            .menu
+            .iter()
+            .copied()
+            .filter(|&action| action != Action::Comfort || self.needs_comfort())
+            .collect()
+    }
+
+    // The action that helps the lowest stat, if any stat is below the healthy level
+    fn primary_need(&self) -> Option<Action> {
+        let lowest = Stat::ALL.into_iter().min_by_key(|&stat| self.stat(stat))?;
+        if self.stat(lowest) > HEALTHY_STAT_THRESHOLD {
+            return None;
+        }
+        Some(match lowest {
+            Stat::Hunger => Action::Feed,
+            Stat::Happiness => Action::Play,
+            Stat::Energy => Action::Sleep,
+            Stat::Health => Action::Heal,
+            Stat::Cleanliness => Action::Clean,
+        })
+    }
+
+    // Heal the Nybbler
+    fn heal(&mut self) {
+        self.health = 100;
+        self.finish_action();
+    }
+
+    // Give the Nybbler a bath
+    fn clean(&mut self) {
+        self.cleanliness = 100;
+        self.happiness = self.happiness.saturating_add(CLEAN_HAPPINESS).min(100);
+        self.finish_action();
+    }
+
+    // Carry out a care action without any prompts; other actions do nothing
+    fn perform<R: Rng>(&mut self, action: Action, rng: &mut R) {
+        match action {
+            Action::Feed => self.feed(),
+            Action::Treat => {
+                self.treat();
+            },
+            Action::Play => self.play(Toy::default()),
+            Action::Sleep => self.sleep(),
+            Action::Heal => self.heal(),
+            Action::Clean => self.clean(),
+            Action::Comfort => {
+                self.comfort(rng);
+            },
+            Action::Notes | Action::SwitchPet | Action::Help | Action::Exit => return,
+        }
+        self.record(action);
+    }
+
+    // Get the Nybbler's current life stage
+    fn life_stage(&self) -> LifeStage {
+        LifeStage::from_age(self.age)
+    }
+
+    // Drop everything the owner wrote themselves, keeping the pet itself intact
+    fn anonymize(&mut self) {
+        self.notes.clear();
+    }
+
+    // The first line of the notes, shortened to fit compact views
+    fn notes_preview(&self, max_width: usize) -> Option<String> {
+        let mut lines = self.notes.lines().map(str::trim).filter(|line| !line.is_empty());
+        let first_line = lines.next()?;
+        let has_more = lines.next().is_some();
+
+        let preview = console::truncate_str(first_line, max_width, "…");
+        if has_more && !preview.ends_with('…') {
+            Some(format!("{}…", preview))
+        } else {
+            Some(preview.into_owned())
+        }
+    }
+
+    // The name shortened to fit headers and banners; saves always use the full name
+    fn display_name(&self) -> Cow<'_, str> {
+        console::truncate_str(&self.name, NAME_DISPLAY_WIDTH, "…")
+    }
+
+    // The Nybbler's name drawn in its signature color
+    fn styled_name(&self) -> console::StyledObject<Cow<'_, str>> {
+        style(self.display_name()).bold().fg(self.color.color())
+    }
+
+    // The character art for the current mood, sized for the life stage
+    fn mood_art(&self, render: Render) -> String {
+        let art = match self.mood {
+            NybblerMood::Sleeping => self.character_type.sleeping(),
+            _ => self.character_type.neutral(),
+        };
+        self.life_stage().frame_art(&render.art(art))
+    }
+
+    // Get the current value of a stat
+    fn stat(&self, stat: Stat) -> u8 {
+        match stat {
+            Stat::Hunger => self.hunger,
+            Stat::Happiness => self.happiness,
+            Stat::Energy => self.energy,
+            Stat::Health => self.health,
+            Stat::Cleanliness => self.cleanliness,
+        }
+    }
+
+    // Get a mutable reference to a stat
+    fn stat_mut(&mut self, stat: Stat) -> &mut u8 {
+        match stat {
+            Stat::Hunger => &mut self.hunger,
+            Stat::Happiness => &mut self.happiness,
+            Stat::Energy => &mut self.energy,
+            Stat::Health => &mut self.health,
+            Stat::Cleanliness => &mut self.cleanliness,
+        }
+    }
+
+    // Move a stat by a possibly fractional amount, keeping whatever doesn't
+    // make up a whole point to add to the next change
+    fn shift_stat(&mut self, stat: Stat, amount: f64) {
+        let total = self.stat_carry[stat as usize] + amount;
+        // Many small steps can land a hair short of a whole point, which still counts
+        let whole = if (total - total.round()).abs() < 1e-9 { total.round() } else { total.trunc() };
+        self.stat_carry[stat as usize] = total - whole;
+        let value = self.stat_mut(stat);
+        *value = (f64::from(*value) + whole).clamp(0.0, 100.0) as u8;
+    }
+
+    // Get the value of a stat as it is shown to the player
+    fn shown_stat(&self, stat: Stat, render: Render) -> u8 {
+        match stat {
+            Stat::Hunger => render.hunger.show(self.hunger),
+            _ => self.stat(stat),
+        }
+    }
+
+    // Every stat as shown to the player, in display order
+    fn shown_stats(&self, render: Render) -> [u8; 5] {
+        Stat::ALL.map(|stat| self.shown_stat(stat, render))
+    }
+
+    // Whether the art should pulse, which plain and screen-reader output skip
+    fn shows_heartbeat(&self, render: Render) -> bool {
+        self.health < HEARTBEAT_HEALTH && !render.ascii && !render.accessible
+    }
+
+    // The stats that have fallen below this Nybbler's alert threshold
+    fn critical_stats(&self) -> Vec<Stat> {
+        Stat::ALL.into_iter().filter(|&stat| self.stat(stat) < self.alert_threshold).collect()
+    }
+
+    // Overall wellbeing as the average of the core stats
+    fn wellness(&self) -> u8 {
+        average_stat([self.hunger, self.happiness, self.energy, self.health])
+    }
+
+    // Whether wellness has gone up or down since the snapshot before the latest one
+    fn wellness_trend(&self) -> Option<Trend> {
+        let earlier = self.snapshots.iter().rev().nth(1)?;
+        Some(Trend::between(earlier.wellness(), self.wellness()))
+    }
+
+    // Check if the Nybbler is alive
+    fn is_alive(&self) -> bool {
+        self.health > 0
+    }
+}
+
+// Average of the four core stats
+fn average_stat(stats: [u8; 4]) -> u8 {
+    let total: u16 = stats.into_iter().map(u16::from).sum();
+    (total / 4) as u8
+}
+
+// Check that a pet name is usable, ignoring surrounding whitespace
+fn validate_name(name: &str) -> Result<(), &'static str> {
+    let name = name.trim();
+    if name.is_empty() {
+        Err("Please enter a name for your Nybbler")
+    } else if name.chars().any(|c| c.is_control() || "/\\:*?\"<>|".contains(c)) {
+        Err("Names can't contain control characters or any of / \\ : * ? \" < > |")
+    } else {
+        Ok(())
+    }
+}
+
+// Pick a cute name that doesn't belong to an existing save
+fn random_name<R: Rng>(rng: &mut R) -> String {
+    let free: Vec<&str> = PET_NAMES.iter().copied().filter(|name| !Nybbler::save_exists(name)).collect();
+    if let Some(name) = free.choose(rng) {
+        return name.to_string();
+    }
+
+    // Every name is taken, so keep numbering until one is free
+    loop {
+        let name = format!("{} {}", PET_NAMES.choose(rng).unwrap(), rng.gen_range(2..1000));
+        if !Nybbler::save_exists(&name) {
+            return name;
+        }
+    }
+}
+
+// Why a Nybbler couldn't be loaded or saved, so callers can react to each case
+#[derive(Debug)]
+enum SaveError {
+    // There's no save at this path
+    NotFound(PathBuf),
+    // The file couldn't be read or written, such as for missing permissions
+    Io(io::Error),
+    // The file exists but isn't a valid save
+    Parse(serde_json::Error),
+    // The name can't be used for a save file
+    InvalidName(&'static str),
+    // Another session holds the lock on this save
+    Locked,
+}
+
+impl std::fmt::Display for SaveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            SaveError::NotFound(path) => write!(f, "no save found at {}", path.display()),
+            SaveError::Io(e) => write!(f, "{}", e),
+            SaveError::Parse(e) => write!(f, "the save is damaged: {}", e),
+            SaveError::InvalidName(reason) => write!(f, "{}", reason),
+            SaveError::Locked => write!(f, "it's open in another nybbler session"),
+        }
+    }
+}
+
+impl std::error::Error for SaveError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SaveError::Io(e) => Some(e),
+            SaveError::Parse(e) => Some(e),
+            SaveError::NotFound(_) | SaveError::InvalidName(_) | SaveError::Locked => None,
+        }
+    }
+}
+
+impl From<io::Error> for SaveError {
+    fn from(error: io::Error) -> Self {
+        SaveError::Io(error)
+    }
+}
+
+// Lets code that only deals in io::Result keep using `?` on saves and loads
+impl From<SaveError> for io::Error {
+    fn from(error: SaveError) -> Self {
+        let kind = match &error {
+            SaveError::NotFound(_) => ErrorKind::NotFound,
+            SaveError::Io(e) => return io::Error::new(e.kind(), error),
+            SaveError::Parse(_) => ErrorKind::InvalidData,
+            SaveError::InvalidName(_) => ErrorKind::InvalidInput,
+            SaveError::Locked => ErrorKind::ResourceBusy,
+        };
+        io::Error::new(kind, error)
+    }
+}
+
+// Report a failed load or save and exit, pointing missing pets at `nybbler list`
+fn exit_with_save_error(name: &str, doing: &str, error: SaveError) -> ! {
+    match error {
+        SaveError::NotFound(_) => {
+            report_missing_pet(name);
+            process::exit(EXIT_NOT_FOUND);
+        },
+        SaveError::InvalidName(reason) => eprintln!("{}", reason),
+        SaveError::Parse(e) => eprintln!("{}'s save is damaged and couldn't be read: {}", name.trim(), e),
+        SaveError::Io(e) => eprintln!("Error {} {}: {}", doing, name.trim(), e),
+        SaveError::Locked => eprintln!("{} is open in another nybbler session; close it there first.", name.trim()),
+    }
+    process::exit(1);
+}
+
+// Held while a pet is open for changes, so a live session, the server, and
+// bulk updates never save over one another
+// The lock is on `<name>.lock` beside the save, and is released when this is
+// dropped, or by the OS if nybbler exits or crashes first
+struct PetLock {
+    _file: fs::File,
+}
+
+impl PetLock {
+    // Lock the named pet, whether or not it has been saved yet
+    fn for_pet(name: &str) -> Result<Self, SaveError> {
+        validate_name(name).map_err(SaveError::InvalidName)?;
+        Self::acquire(&get_save_directory()?.join(save_file_name(name)))
+    }
+
+    // Lock the pet saved at `path`, refusing if someone else already holds it
+    fn acquire(path: &Path) -> Result<Self, SaveError> {
+        let file = fs::OpenOptions::new().create(true).truncate(false).write(true).open(path.with_extension("lock"))?;
+        match file.try_lock() {
+            Ok(()) => Ok(PetLock { _file: file }),
+            Err(fs::TryLockError::WouldBlock) => Err(SaveError::Locked),
+            Err(fs::TryLockError::Error(e)) => Err(SaveError::Io(e)),
+        }
+    }
+
+    // Whether another session holds the lock on the pet saved at `path` right now
+    // Checking never leaves a lock file behind for a pet that was never locked
+    fn is_held(path: &Path) -> bool {
+        path.with_extension("lock").exists() && matches!(Self::acquire(path), Err(SaveError::Locked))
+    }
+}
+
+// Move a save that couldn't be parsed aside to `<name>.json.bak`, which isn't listed as a pet
+fn back_up_damaged_save(path: &Path) -> io::Result<PathBuf> {
+    let backup = path.with_extension("json.bak");
+    fs::rename(path, &backup)?;
+    Ok(backup)
+}
+
+// File name of the save for a Nybbler; names differing only by case or
+// surrounding whitespace share a save
+fn save_file_name(name: &str) -> String {
+    format!("{}.json", name.trim().to_lowercase())
+}
+
+// Give a saved pet a new name, moving it to the save file for that name
+// A different pet already saved under the new name is never overwritten
+fn rename_save(dir: &Path, old: &str, new: &str) -> Result<Nybbler, SaveError> {
+    validate_name(old).map_err(SaveError::InvalidName)?;
+    validate_name(new).map_err(SaveError::InvalidName)?;
+    let (source, target) = (dir.join(save_file_name(old)), dir.join(save_file_name(new)));
+    let mut nybbler = Nybbler::load_path(&source)?;
+    // Changing only the capitalization keeps the same file
+    let moving = source != target;
+    if moving && target.exists() {
+        let message = format!("a Nybbler named {} already exists", new.trim());
+        return Err(SaveError::Io(io::Error::new(ErrorKind::AlreadyExists, message)));
+    }
+
+    nybbler.name = new.trim().to_string();
+    nybbler.save_path(&target)?;
+    if moving {
+        fs::remove_file(&source)?;
+    }
+    Ok(nybbler)
+}
+
+// Rename saves whose file names have capitals to the lowercase names save() and load() use,
+// so saves behave the same on case-sensitive and case-insensitive filesystems
+// Files whose lowercase name is already taken are left for `dedupe`; returns the renamed files
+fn normalize_save_file_names(dir: &Path) -> io::Result<Vec<String>> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let names: BTreeSet<String> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .collect();
+    let mut renamed = Vec::new();
+    for name in &names {
+        let lower = name.to_lowercase();
+        if lower == *name || !lower.ends_with(".json") || names.contains(&lower) || !dir.join(name).is_file() {
+            continue;
+        }
+
+        // Go through a temporary name so case-insensitive filesystems really change the case
+        let temp = dir.join(format!("{}.renaming", lower));
+        fs::rename(dir.join(name), &temp)?;
+        fs::rename(&temp, dir.join(&lower))?;
+        renamed.push(name.clone());
+    }
+    Ok(renamed)
+}
+
+// Subfolder of the save directory holding abandoned Nybblers
+const ARCHIVE_DIR: &str = "archived";
+
+// Move a save between folders without overwriting one that's already there
+fn move_save(from: &Path, to: &Path, name: &str) -> io::Result<()> {
+    let file_name = save_file_name(name);
+    let (source, target) = (from.join(&file_name), to.join(&file_name));
+    if !source.exists() {
+        let message = format!("No Nybbler named {} was found in {}", name.trim(), from.display());
+        return Err(io::Error::new(ErrorKind::NotFound, message));
+    }
+    if target.exists() {
+        let message = format!("A Nybbler named {} is already in {}", name.trim(), to.display());
+        return Err(io::Error::new(ErrorKind::AlreadyExists, message));
+    }
+
+    fs::create_dir_all(to)?;
+    fs::rename(source, target)
+}
+
+// Shared state threaded through a session, such as the random number generator
+struct GameContext {
+    rng: StdRng,
+    config: Config,
+    render: Render,
+}
+
+impl GameContext {
+    // Create a context, seeding the RNG when a seed is given for reproducible sessions
+    fn new(seed: Option<u64>, config: Config, render: Render) -> Self {
+        let rng = match seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        GameContext { rng, config, render }
+    }
+}
+
+// Work out where saves live without creating anything
+fn resolve_save_directory() -> io::Result<PathBuf> {
+    let working_dir = env::current_dir().unwrap_or_default();
+    let save_dir = choose_save_directory(env::var_os(DATA_DIR_ENV), data_dir(), &working_dir);
+    check_save_directory(&save_dir)?;
+
+    Ok(save_dir)
+}
+
+// Pick the save folder: the environment override if set, then the platform's
+// data directory, then a hidden folder in the working directory on systems without one
+fn choose_save_directory(override_dir: Option<OsString>, data_dir: Option<PathBuf>, working_dir: &Path) -> PathBuf {
+    match (override_dir.filter(|dir| !dir.is_empty()), data_dir) {
+        (Some(dir), _) => PathBuf::from(dir),
+        (None, Some(data_dir)) => data_dir.join("nybbler"),
+        (None, None) => working_dir.join(FALLBACK_SAVE_DIR),
+    }
+}
+
+// Explain a save directory that can't be used, instead of failing confusingly later
+fn check_save_directory(path: &Path) -> io::Result<()> {
+    let error = match fs::metadata(path) {
+        Ok(metadata) if metadata.is_dir() => return Ok(()),
+        Ok(_) => format!(
+            "{} is a file, but Nybbler keeps its saves in a folder there. Move or rename the file and try again.",
+            path.display()
+        ),
+        // Following a symlink fails for both dangling links and loops
+        Err(e) if fs::symlink_metadata(path).is_ok_and(|metadata| metadata.file_type().is_symlink()) => format!(
+            "{} is a symlink that can't be followed ({}). Point it at a real folder or remove it and try again.",
+            path.display(),
+            e
+        ),
+        // Nothing there yet is fine; the folder is created when it's needed
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(()),
+        Err(e) => format!("Could not use the save folder {}: {}", path.display(), e),
+    };
+
+    Err(io::Error::other(error))
+}
+
+// Helper function to get the save directory
+fn get_save_directory() -> io::Result<PathBuf> {
+    let save_dir = resolve_save_directory()?;
+
+    if !save_dir.exists() {
+        fs::create_dir_all(&save_dir)?;
+    }
+
+    Ok(save_dir)
+}
+
+// List every Nybbler save file in the save directory, sorted by path
+fn list_save_files() -> io::Result<Vec<PathBuf>> {
+    // A missing save directory just means there are no pets yet
+    let save_dir = resolve_save_directory()?;
+    if !save_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(save_dir)? {
+        let path = entry?.path();
+        let is_config = path.file_name().is_some_and(|name| name == config::CONFIG_FILE);
+        // Only files count, so the archive folder is never listed or deleted
+        if path.is_file() && path.extension().is_some_and(|ext| ext == "json") && !is_config {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    Ok(paths)
+}
+
+// Exit with directions to the scripting commands when no one can answer prompts
+fn require_terminal() {
+    if !io::stdin().is_terminal() {
+        eprintln!("Nybbler needs an interactive terminal for this, but input isn't coming from one.");
+        eprintln!("From scripts, try `nybbler --once --name <name>`, `nybbler stats <name>`, or `nybbler list`,");
+        eprintln!("and pass `--yes` to commands that would otherwise ask for confirmation.");
+        process::exit(EXIT_NO_TERMINAL);
+    }
+}
+
+// With a cap on saved pets in the config, archive the ones played least recently to
+// make room for a new pet, asking first unless `skip_confirm`; returns whether there's room
+fn make_room_for_new_pet(config: &Config, render: Render, skip_confirm: bool) -> io::Result<bool> {
+    let Some(max_pets) = config.max_pets else {
+        return Ok(true);
+    };
+    let pets: Vec<Nybbler> = list_save_files()?.iter().filter_map(|path| Nybbler::read_path(path).ok()).collect();
+    let Some(to_archive) = pets_to_prune(pets, max_pets) else {
+        eprintln!(
+            "There's no room for another pet: your favorite is never archived automatically. Raise max_pets in {}.",
+            config::CONFIG_FILE
+        );
+        return Ok(false);
+    };
+    if to_archive.is_empty() {
+        return Ok(true);
+    }
+
+    let names = join_clauses(&to_archive);
+    println!("You can keep at most {} pets, so {} would be archived to make room.", max_pets, names);
+    if !skip_confirm {
+        if !io::stdin().is_terminal() {
+            eprintln!("Pass --yes to go ahead, or raise max_pets in {}.", config::CONFIG_FILE);
+            return Ok(false);
+        }
+        let proceed = dialoguer::Confirm::new().with_prompt(format!("Archive {}?", names)).default(false).interact()?;
+        if !proceed {
+            println!("Nothing was archived, so no new pet was created.");
+            return Ok(false);
+        }
+    }
+
+    let save_dir = get_save_directory()?;
+    for name in &to_archive {
+        move_save(&save_dir, &save_dir.join(ARCHIVE_DIR), name)?;
+        let archived = format!("{} has been archived. Run `nybbler unarchive {}` to bring them back.", name, name);
+        println!("{}", render.labeled("📦", &archived));
+    }
+    Ok(true)
+}
+
+// Names of the pets to archive so one more fits under `max_pets`, least recently played
+// first; favorites are never picked, so None means there's no way to make room
+fn pets_to_prune(mut pets: Vec<Nybbler>, max_pets: usize) -> Option<Vec<String>> {
+    let excess = (pets.len() + 1).saturating_sub(max_pets.max(1));
+    pets.retain(|pet| !pet.is_favorite);
+    if pets.len() < excess {
+        return None;
+    }
+    pets.sort_by_key(|pet| pet.last_updated);
+    Some(pets.into_iter().take(excess).map(|pet| pet.name).collect())
+}
+
+// Name of the pet to resume by default: the favorite, or else the one updated most recently
+fn last_played_name() -> io::Result<Option<String>> {
+    let mut latest: Option<Nybbler> = None;
+    for path in list_save_files()? {
+        let Ok(nybbler) = Nybbler::read_path(&path) else {
+            continue;
+        };
+        if nybbler.is_favorite {
+            return Ok(Some(nybbler.name));
+        }
+        if latest.as_ref().is_none_or(|best| nybbler.last_updated > best.last_updated) {
+            latest = Some(nybbler);
+        }
+    }
+
+    Ok(latest.map(|nybbler| nybbler.name))
+}
+
+// Make the named pet the only favorite among the given saves, returning whether it was found
+fn set_favorite(paths: &[PathBuf], name: &str) -> io::Result<bool> {
+    let file_name = save_file_name(name);
+    if !paths.iter().any(|path| path.file_name().is_some_and(|n| *n == *file_name)) {
+        return Ok(false);
+    }
+
+    for path in paths {
+        let Ok(mut nybbler) = Nybbler::load_path(path) else {
+            continue;
+        };
+        let favorite = path.file_name().is_some_and(|n| *n == *file_name);
+        // Only touch the saves whose star actually changes
+        if nybbler.is_favorite != favorite {
+            nybbler.is_favorite = favorite;
+            nybbler.save_path(path)?;
+        }
+    }
+    Ok(true)
+}
+
+// Print every saved Nybbler without changing any of them
+fn print_list(render: Render) -> io::Result<()> {
+    let paths = list_save_files()?;
+    if paths.is_empty() {
+        println!("{}", NO_PETS_HINT);
+        return Ok(());
+    }
+
+    for path in paths {
+        // A pet open in another session may have changes it hasn't saved yet
+        if PetLock::is_held(&path) {
+            eprintln!("Skipping {}: {}", path.display(), SaveError::Locked);
+            continue;
+        }
+        match Nybbler::read_path(&path) {
+            Ok(nybbler) => println!(
+                "{} {}{} - {} days old, {:?}",
+                nybbler.mood.glyph(render),
+                nybbler.styled_name(),
+                if nybbler.is_favorite { render.pick(" ⭐", " (favorite)") } else { "" },
+                nybbler.age,
+                nybbler.mood
+            ),
+            Err(e) => eprintln!("Skipping {}: {}", path.display(), e),
+        }
+    }
+
+    Ok(())
+}
+
+// Compact plain-text status, one stat per line, for scripts and status bars
+// The keys and values never change with display settings, so scrapers keep working
+fn render_status(nybbler: &Nybbler) -> String {
+    format!(
+        "name: {}\nhunger: {}\nhappiness: {}\nenergy: {}\nhealth: {}\ncleanliness: {}\nage: {}\nmood: {:?}",
+        nybbler.name, nybbler.hunger, nybbler.happiness, nybbler.energy, nybbler.health, nybbler.cleanliness, nybbler.age, nybbler.mood
+    )
+}
+
+// Bring a pet saved in `dir` up to date, save it, and return its status,
+// as `status` and `--once` both print
+fn refresh_status(dir: &Path, name: &str, config: &Config) -> Result<String, SaveError> {
+    validate_name(name).map_err(SaveError::InvalidName)?;
+    let path = dir.join(save_file_name(name));
+    let (mut nybbler, _lock) = Nybbler::load_locked(&path)?;
+    nybbler.update(config);
+    nybbler.save_path(&path)?;
+    Ok(render_status(&nybbler))
+}
+
+// Explain a missing save, pointing first-time players at the game
+fn report_missing_pet(name: &str) {
+    if list_save_files().map(|paths| paths.is_empty()).unwrap_or(true) {
+        eprintln!("{}", NO_PETS_HINT);
+    } else {
+        eprintln!("No Nybbler named {} was found. Run `nybbler list` to see your pets.", name.trim());
+    }
+}
+
+// Delete all Nybbler save files
+fn delete_all_nybblers() -> io::Result<usize> {
+    let paths = list_save_files()?;
+    for path in &paths {
+        fs::remove_file(path)?;
+    }
+
+    Ok(paths.len())
+}
+
+// Check a save file without importing it, returning the pet and any problems that were fixed up
+fn inspect_save(path: &Path) -> io::Result<(Nybbler, Vec<String>)> {
+    let data = fs::read_to_string(path)?;
+    let (nybbler, clamped) = Nybbler::from_json(&data).map_err(|e| io::Error::new(ErrorKind::InvalidData, e))?;
+    if let Err(e) = validate_name(&nybbler.name) {
+        return Err(io::Error::new(ErrorKind::InvalidData, e));
+    }
+
+    let warnings = clamped
+        .into_iter()
+        .map(|stat| format!("{} was out of range and would be clamped to 100", stat))
+        .collect();
+    Ok((nybbler, warnings))
+}
+
+// Turn save JSON from another machine into a pet called `name`, clamping any out-of-range stats
+fn parse_import(data: &str, name: &str) -> Result<Nybbler, String> {
+    validate_name(name)?;
+    let (mut nybbler, clamped) = Nybbler::from_json(data).map_err(|e| format!("not a valid Nybbler ({})", e))?;
+    for stat in clamped {
+        eprintln!("Warning: {} was out of range and has been clamped to 100", stat);
+    }
+    nybbler.name = name.trim().to_string();
+    // Being the favorite belongs to the collection it came from
+    nybbler.is_favorite = false;
+    Ok(nybbler)
+}
+
+// Import every save file in a directory, returning (imported, skipped, failed) counts
+fn import_directory(dir: &Path, render: Render, skip_confirm: bool) -> io::Result<(usize, usize, usize)> {
+    let mut paths: Vec<PathBuf> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    paths.sort();
+
+    let mut counts = (0, 0, 0);
+    for path in paths {
+        // One bad file shouldn't stop the rest from importing
+        let pet = Nybbler::load_path(&path).map_err(|e| format!("not a valid Nybbler ({})", e));
+        import_pet(pet, &path.display().to_string(), render, skip_confirm, &mut counts)?;
+    }
+
+    Ok(counts)
+}
+
+// Save one imported or restored pet, asking before replacing an existing one,
+// and count it as imported, skipped, or failed
+fn import_pet(
+    pet: Result<Nybbler, String>,
+    source: &str,
+    render: Render,
+    skip_confirm: bool,
+    (imported, skipped, failed): &mut (usize, usize, usize),
+) -> io::Result<()> {
+    let mut nybbler = match pet.and_then(|nybbler| validate_name(&nybbler.name).map(|()| nybbler).map_err(String::from)) {
+        Ok(nybbler) => nybbler,
+        Err(e) => {
+            println!("{} {}: {}", render.pick("❌", "Failed:"), source, e);
+            *failed += 1;
+            return Ok(());
+        }
+    };
+    // Being the favorite belongs to the collection it came from
+    nybbler.is_favorite = false;
+
+    if Nybbler::save_exists(&nybbler.name) && !skip_confirm {
+        let overwrite = dialoguer::Confirm::new()
+            .with_prompt(format!("A Nybbler named {} already exists! Overwrite it?", nybbler.name))
+            .default(false)
+            .interact()?;
+        if !overwrite {
+            println!("{}", render.labeled("⏭️", &format!("Skipped {}", nybbler.name)));
+            *skipped += 1;
+            return Ok(());
+        }
+    }
+
+    match nybbler.save() {
+        Ok(()) => {
+            println!("{}", render.labeled("✅", &format!("Imported {}", nybbler.styled_name())));
+            *imported += 1;
+        },
+        Err(e) => {
+            println!("{} {}: could not save ({})", render.pick("❌", "Failed:"), source, e);
+            *failed += 1;
+        }
+    }
+    Ok(())
+}
+
+// Write every readable save into one JSON array, returning how many were backed up and skipped
+fn write_backup(paths: &[PathBuf], out: &Path) -> io::Result<(usize, usize)> {
+    let mut pets = Vec::new();
+    for path in paths {
+        match Nybbler::read_path(path) {
+            Ok(nybbler) => pets.push(nybbler),
+            Err(e) => eprintln!("Skipping {}: {}", path.display(), e),
+        }
+    }
+    let json = serde_json::to_string_pretty(&pets).map_err(io::Error::other)?;
+    fs::write(out, json)?;
+    Ok((pets.len(), paths.len() - pets.len()))
+}
+
+// Read the pets in a backup, checking each entry on its own so one bad pet doesn't spoil the rest
+fn read_backup(path: &Path) -> io::Result<Vec<Result<Nybbler, String>>> {
+    let data = fs::read_to_string(path)?;
+    let entries: Vec<serde_json::Value> =
+        serde_json::from_str(&data).map_err(|e| io::Error::new(ErrorKind::InvalidData, e))?;
+    Ok(entries
+        .into_iter()
+        .map(|entry| {
+            let (nybbler, clamped) = Nybbler::from_json(&entry.to_string()).map_err(|e| format!("not a valid Nybbler ({})", e))?;
+            for stat in clamped {
+                eprintln!("Warning: {}'s {} was out of range and has been clamped to 100", nybbler.name, stat);
+            }
+            Ok(nybbler)
+        })
+        .collect())
+}
+
+// Print where saves live and whether that location is usable
+fn print_save_directory() -> io::Result<()> {
+    let save_dir = resolve_save_directory()?;
+    let save_dir = save_dir.canonicalize().unwrap_or(save_dir);
+
+    println!("{}", save_dir.display());
+    println!("Exists: {}", if save_dir.is_dir() { "yes" } else { "no" });
+
+    // Probe with a throwaway file since permission bits don't tell the whole story
+    let probe = save_dir.join(".nybbler-write-test");
+    let writable = fs::OpenOptions::new().write(true).create_new(true).open(&probe).is_ok();
+    if writable {
+        let _ = fs::remove_file(&probe);
+    }
+    println!("Writable: {}", if writable { "yes" } else { "no" });
+
+    Ok(())
+}
+
+// Summarize every saved Nybbler, updating each one as it is loaded
+fn print_household(config: &Config, render: Render) -> io::Result<()> {
+    let mut pets = Vec::new();
+    for path in list_save_files()? {
+        match Nybbler::load_path(&path) {
+            Ok(mut nybbler) => {
+                nybbler.update(config);
+                nybbler.save()?;
+                pets.push(nybbler);
+            },
+            Err(e) => eprintln!("Skipping {}: {}", path.display(), e),
+        }
+    }
+
+    if pets.is_empty() {
+        println!("{}", render.labeled("🏠", "Your household is empty. Run the game to create a Nybbler!"));
+        return Ok(());
+    }
+
+    let alive = pets.iter().filter(|n| n.is_alive()).count();
+    let average_wellness = pets.iter().map(|n| u32::from(n.wellness())).sum::<u32>() / pets.len() as u32;
+
+    println!("{}", style(render.framed("🏠", "Nybbler Household")).bold().magenta());
+    println!("Total pets: {}", pets.len());
+    println!("Alive: {}   Passed away: {}", alive, pets.len() - alive);
+    println!("Average wellness: {}/100", average_wellness);
+    if let Some(oldest) = pets.iter().max_by_key(|n| n.age) {
+        println!("Oldest pet: {} ({} days)", style(&oldest.name).bold().yellow(), oldest.age);
+    }
+
+    Ok(())
+}
+
+// Merge save files whose names only differ by case, returning how many were removed
+fn dedupe_nybblers(render: Render) -> io::Result<usize> {
+    let save_dir = get_save_directory()?;
+
+    // Group save files by their lowercased file name
+    let mut groups: BTreeMap<String, Vec<PathBuf>> = BTreeMap::new();
+    for path in list_save_files()? {
+        if let Some(file_name) = path.file_name().and_then(|n| n.to_str()) {
+            groups.entry(file_name.to_lowercase()).or_default().push(path);
+        }
+    }
+
+    let mut removed = 0;
+    for (file_name, mut paths) in groups {
+        paths.sort();
+
+        let keep = if paths.len() > 1 {
+            let found = format!("Found {} saves for {}:", paths.len(), style(&file_name).bold().yellow());
+            println!("{}", render.labeled("🔍", &found));
+
+            // Describe each candidate so the player can choose which one to keep
+            let options: Vec<String> = paths
+                .iter()
+                .map(|path| {
+                    let label = path.file_name().unwrap_or_default().to_string_lossy();
+                    match Nybbler::load_path(path) {
+                        Ok(n) => format!(
+                            "{} - {} days old, hunger {}, happiness {}, energy {}, health {} (last played {})",
+                            label, n.age, n.hunger, n.happiness, n.energy, n.health,
+                            n.last_updated.format("%Y-%m-%d %H:%M")
+                        ),
+                        Err(e) => format!("{} - unreadable: {}", label, e),
+                    }
+                })
+                .collect();
+
+            let selection = Select::with_theme(&ColorfulTheme::default())
+                .with_prompt("Which save would you like to keep?")
+                .items(&options)
+                .default(0)
+                .interact()?;
+
+            for (i, path) in paths.iter().enumerate() {
+                if i != selection {
+                    fs::remove_file(path)?;
+                    removed += 1;
+                }
+            }
+            paths.swap_remove(selection)
+        } else {
+            paths.remove(0)
+        };
+
+        // Normalize the kept file name to lowercase to prevent future collisions
+        let normalized = save_dir.join(&file_name);
+        if keep != normalized {
+            fs::rename(&keep, &normalized)?;
+        }
+    }
+
+    Ok(removed)
+}
+
+// Say how many times something happened in words
+fn times(count: usize) -> String {
+    match count {
+        1 => "once".to_string(),
+        2 => "twice".to_string(),
+        n => format!("{} times", n),
+    }
+}
+
+// Join clauses into a sentence like "a, b and c"
+fn join_clauses(clauses: &[String]) -> String {
+    match clauses {
+        [] => String::new(),
+        [only] => only.clone(),
+        [rest @ .., last] => format!("{} and {}", rest.join(", "), last),
+    }
+}
+
+// Write one diary entry per day that has any recorded moods or actions
+fn diary_entries(nybbler: &Nybbler) -> Vec<String> {
+    let mut days: BTreeMap<NaiveDate, (Vec<NybblerMood>, Vec<Action>)> = BTreeMap::new();
+    for entry in &nybbler.mood_history {
+        days.entry(entry.at.date_naive()).or_default().0.push(entry.mood);
+    }
+    for event in &nybbler.events {
+        days.entry(event.at.date_naive()).or_default().1.push(event.action);
+    }
+
+    let Some(&first_day) = days.keys().next() else {
+        return Vec::new();
+    };
+
+    days.iter()
+        .map(|(date, (moods, actions))| {
+            let mut clauses = Vec::new();
+
+            // The last mood of the day other than being sick sets the tone
+            if let Some(mood) = moods.iter().rev().find(|&&mood| mood != NybblerMood::Sick) {
+                clauses.push(format!("was {}", mood.describe()));
+            }
+
+            for action in Action::ALL {
+                let count = actions.iter().filter(|&&done| done == action).count();
+                if let (Some(verb), 1..) = (action.past_tense(), count) {
+                    clauses.push(format!("{} {}", verb, times(count)));
+                }
+            }
+
+            if moods.contains(&NybblerMood::Sick) {
+                if moods.last() == Some(&NybblerMood::Sick) {
+                    clauses.push("caught a cold".to_string());
+                } else {
+                    clauses.push("caught a cold but recovered".to_string());
+                }
+            }
+
+            let day = (*date - first_day).num_days() + 1;
+            format!("Day {} ({}): {} {}.", day, date.format("%Y-%m-%d"), nybbler.name, join_clauses(&clauses))
+        })
+        .collect()
+}
+
+// Show the level with a bar of progress toward the next one
+fn level_line(nybbler: &Nybbler, render: Render) -> String {
+    let needed = nybbler.xp_to_next_level();
+    let filled = (nybbler.xp.min(needed) * 10 / needed) as usize;
+    let (full, empty) = (render.pick("█", "#"), render.pick("░", "-"));
+    let line = format!("Level {} [{}{}] {}/{} XP", nybbler.level, full.repeat(filled), empty.repeat(10 - filled), nybbler.xp, needed);
+    render.labeled("⭐", &line)
+}
+
+// Celebrate a Nybbler reaching a new level
+fn level_up_message(nybbler: &Nybbler, render: Render) -> String {
+    let message = format!("Level up! {} is now level {} and gets a little more out of your care!", nybbler.display_name(), nybbler.level);
+    if render.ascii {
+        message
+    } else {
+        format!("🌟 {} 🌟", message)
+    }
+}
+
+// Celebrate a Nybbler growing closer to its owner
+fn bond_message(nybbler: &Nybbler, render: Render) -> String {
+    let message = format!(
+        "You and {} {} now! It frets a little less while you're away.",
+        nybbler.display_name(),
+        nybbler.bond_tier().describe()
+    );
+    render.labeled("💞", &message)
+}
+
+// Draw stats as plain text in the layout the display flags ask for
+fn text_stats(values: &[(Stat, u8)], render: Render) -> Vec<String> {
+    let width = Stat::label_width(render);
+    if render.numeric {
+        return values.iter().map(|&(stat, value)| format!("{:<width$} {:>3}/100", stat.label(render), value)).collect();
+    }
+    if render.bars == BarLayout::Horizontal {
+        return values.iter().map(|&(stat, value)| text_stat_bar(stat.label(render), value, render)).collect();
+    }
+
+    // Side-by-side columns, each row standing for ten points
+    let block = render.pick("███", "###");
+    let columns = |cell: &dyn Fn(Stat, u8) -> String| {
+        values.iter().map(|&(stat, value)| format!("{:^width$}", cell(stat, value))).collect::<Vec<_>>().join(" ")
+    };
+    let mut lines: Vec<String> =
+        (0..10u8).rev().map(|row| columns(&|_, value| if value > row * 10 { block.to_string() } else { String::new() })).collect();
+    lines.push(columns(&|_, value| value.to_string()));
+    lines.push(columns(&|stat, _| stat.label(render).to_string()));
+    lines
+}
+
+// Draw a stat as a bar of plain text blocks without any terminal styling
+fn text_stat_bar(label: &str, value: u8, render: Render) -> String {
+    let filled = usize::from(value.min(100)) / 5;
+    let (full, empty) = (render.pick("█", "#"), render.pick("░", "-"));
+    let width = Stat::label_width(render);
+    format!("{:<width$} [{}{}] {:>3}", label, full.repeat(filled), empty.repeat(20 - filled), value)
+}
+
+// Replay a Nybbler's recorded history frame by frame
+fn play_timelapse(nybbler: &Nybbler, term: &Term, render: Render) -> io::Result<()> {
+    // Fall back to the mood history when there aren't enough stat snapshots
+    let frames: Vec<(DateTime<Local>, NybblerMood, Option<&StatSnapshot>)> = if nybbler.snapshots.len() >= 2 {
+        nybbler.snapshots.iter().map(|snap| (snap.at, snap.mood, Some(snap))).collect()
+    } else {
+        nybbler.mood_history.iter().map(|entry| (entry.at, entry.mood, None)).collect()
+    };
+
+    if frames.len() < 2 {
+        let message = format!("{} hasn't lived long enough for a timelapse yet. Check back later!", nybbler.name);
+        println!("{}", render.labeled("📼", &message));
+        return Ok(());
+    }
+
+    for (i, (at, mood, snapshot)) in frames.iter().enumerate() {
+        term.clear_screen()?;
+        let title = format!("{}'s life  ({}/{})", nybbler.name, i + 1, frames.len());
+        println!("{}", style(render.labeled("📼", &title)).bold().magenta());
+        println!("{}", style(at.format("%Y-%m-%d %H:%M")).dim());
+
+        println!("Mood: {} {:?}", mood.glyph(render), mood);
+
+        let art = match mood {
+            NybblerMood::Sleeping => nybbler.character_type.sleeping(),
+            _ => nybbler.character_type.neutral(),
+        };
+        println!("{}", style(render.art(art)).bold().yellow());
+
+        if let Some(snapshot) = snapshot {
+            let values = [
+                (Stat::Hunger, render.hunger.show(snapshot.hunger)),
+                (Stat::Happiness, snapshot.happiness),
+                (Stat::Energy, snapshot.energy),
+                (Stat::Health, snapshot.health),
+            ];
+            println!("{}", text_stats(&values, render).join("\n"));
+        }
+
+        thread::sleep(Duration::from_millis(TIMELAPSE_FRAME_MILLIS));
+    }
+
+    println!();
+    println!("{}", render.labeled("🌈", &format!("That's the story of {} so far!", nybbler.name)));
+    Ok(())
+}
+
+// Render a plain-text trading card for a Nybbler, suitable for pasting into chat
+fn render_card(nybbler: &Nybbler, render: Render) -> String {
+    let title = if render.ascii {
+        format!("* {} the Nybbler *", nybbler.name)
+    } else {
+        format!("✨ {} the Nybbler ✨", nybbler.name)
+    };
+    let mut lines = vec![title, String::new()];
+    lines.extend(nybbler.mood_art(render).lines().filter(|l| !l.trim().is_empty()).map(String::from));
+    lines.push(String::new());
+    lines.push(format!("Age: {} ({:?})", render.age_units.format(nybbler.age), nybbler.life_stage()));
+    lines.push(format!("Mood: {} {:?}", nybbler.mood.glyph(render), nybbler.mood));
+    if let Some(notes) = nybbler.notes_preview(NOTES_PREVIEW_WIDTH) {
+        lines.push(format!("Notes: {}", notes));
+    }
+    lines.push(String::new());
+    lines.extend(text_stats(&Stat::ALL.map(|stat| (stat, nybbler.shown_stat(stat, render))), render));
+
+    // Pad every line to the same visible width so the border lines up
+    let width = lines.iter().map(|l| console::measure_text_width(l)).max().unwrap_or(0);
+    let horizontal = render.pick("═", "-").repeat(width + 2);
+    let vertical = render.pick("║", "|");
+    let mut card = format!("{}{}{}\n", render.pick("╔", "+"), horizontal, render.pick("╗", "+"));
+    for line in &lines {
+        let padding = width - console::measure_text_width(line);
+        card.push_str(&format!("{} {}{} {}\n", vertical, line, " ".repeat(padding), vertical));
+    }
+    card.push_str(&format!("{}{}{}", render.pick("╚", "+"), horizontal, render.pick("╝", "+")));
+    card
+}
+
+// Command line arguments structure
+#[derive(Parser)]
+#[command(name = "nybbler")]
+#[command(about = "🎮 Nybbler: The Terminal Virtual Pet 🐙", long_about = None)]
+struct Cli {
+    /// Use the full-screen view with a stable layout instead of the classic one
+    #[arg(long)]
+    tui: bool,
+
+    /// Refresh the stats every N seconds until a key is pressed, like a desk companion
+    #[arg(long, value_name = "SECONDS")]
+    interval: Option<u64>,
+
+    /// Use plain ASCII output instead of emoji and box-drawing characters
+    #[arg(long, global = true, visible_alias = "no-emoji", conflicts_with = "unicode")]
+    ascii: bool,
+
+    /// Use emoji output even if the terminal doesn't look like it supports it
+    #[arg(long, global = true)]
+    unicode: bool,
+
+    /// Describe the pet in plain sentences for screen readers instead of bars and art
+    #[arg(long, global = true)]
+    accessible: bool,
+
+    /// Draw the stats as horizontal bars or as side-by-side vertical columns
+    #[arg(long, global = true, value_enum, default_value_t = BarLayout::Horizontal)]
+    bars: BarLayout,
+
+    /// Show the stats as a compact table of numbers instead of bars
+    #[arg(long, global = true)]
+    numeric: bool,
+
+    /// Seed the random number generator for a reproducible session
+    #[arg(long, global = true)]
+    seed: Option<u64>,
+
+    /// Append diagnostic details about updates and mood changes to events.log
+    #[arg(long, global = true)]
+    log: bool,
+
+    /// Skip confirmation prompts
+    #[arg(long, short = 'y', global = true)]
+    yes: bool,
+
+    /// Update the pet once, save it, print its status, and exit
+    #[arg(long)]
+    once: bool,
+
+    /// Add a menu option for setting stats and age directly, for testing
+    #[arg(long)]
+    debug: bool,
+
+    /// Pet to update with --once, defaulting to the one played most recently
+    #[arg(long, requires = "once")]
+    name: Option<String>,
+
+    /// Pet to play with, skipping the name prompt. Subcommand names such as
+    /// `list` always run the subcommand, so a pet with one of those names
+    /// needs the prompt instead
+    #[arg(value_name = "NAME", conflicts_with_all = ["once", "name"])]
+    pet: Option<String>,
+
+    #[command(subcommand)]
+    command: Option<Commands>,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Load a Nybbler, or create it if it doesn't exist yet, and print its status
+    Ensure {
+        /// Name of the Nybbler
+        #[arg(long)]
+        name: String,
+    },
+    /// Create and save a new Nybbler without any prompts
+    New {
+        /// Name of the new Nybbler
+        #[arg(long)]
+        name: String,
+        /// Character design, picked at random if not given
+        #[arg(long, value_enum, ignore_case = true)]
+        character: Option<characters::CharacterType>,
+        /// Starting stats for the new Nybbler, instead of the one in the config
+        #[arg(long, value_enum, ignore_case = true)]
+        start: Option<StartingProfile>,
+        /// Replace an existing Nybbler with the same name
+        #[arg(long)]
+        force: bool,
+    },
+    /// Delete all Nybbler pets
+    DeleteAll,
+    /// Move a Nybbler into the archive without deleting it
+    Abandon {
+        /// Name of the Nybbler
+        name: String,
+    },
+    /// Give a saved Nybbler a new name
+    Rename {
+        /// Current name of the Nybbler
+        old: String,
+        /// New name for the Nybbler
+        new: String,
+    },
+    /// Bring an archived Nybbler back
+    Unarchive {
+        /// Name of the Nybbler
+        name: String,
+    },
+    /// Merge save files whose names only differ by case
+    Dedupe,
+    /// Export a saved Nybbler as a file to share or import elsewhere
+    Export {
+        /// Name of the Nybbler
+        name: String,
+        /// File to write, instead of printing the save to stdout
+        #[arg(long)]
+        output: Option<PathBuf>,
+        /// Leave out the notes and anything else the owner wrote
+        #[arg(long)]
+        anonymize: bool,
+    },
+    /// Import a Nybbler from save JSON on stdin, such as the output of `export`
+    Import {
+        /// Name to save the Nybbler under
+        name: String,
+        /// Replace an existing Nybbler with the same name
+        #[arg(long)]
+        force: bool,
+    },
+    /// Import every Nybbler save file from a directory
+    ImportDir {
+        /// Directory containing exported save files
+        path: PathBuf,
+    },
+    /// Save every Nybbler into a single backup file
+    Backup {
+        /// Where to write the backup
+        #[arg(long, default_value = "pets.json")]
+        out: PathBuf,
+    },
+    /// Recreate saves from a backup made with `backup`, asking before overwriting
+    Restore {
+        /// Path to the backup file
+        path: PathBuf,
+    },
+    /// List every saved Nybbler
+    List,
+    /// Mark a Nybbler as your favorite, resumed by default and starred in the list
+    Favorite {
+        /// Name of the Nybbler
+        name: String,
+    },
+    /// Print a saved Nybbler's stats without changing them
+    Stats {
+        /// Name of the Nybbler
+        name: String,
+    },
+    /// Bring a saved Nybbler up to date, save it, and print its stats as plain text
+    Status {
+        /// Name of the Nybbler
+        name: String,
+    },
+    /// Set the stat level below which a Nybbler's stats are flagged as critical
+    SetAlert {
+        /// Name of the Nybbler
+        name: String,
+        /// Stat level from 0 to 100
+        #[arg(value_parser = clap::value_parser!(u8).range(0..=100))]
+        threshold: u8,
+    },
+    /// Permanently advance a saved Nybbler's clock, as if days went by without care
+    FastForward {
+        /// Name of the Nybbler
+        name: String,
+        /// Number of days to skip ahead
+        #[arg(long, value_parser = clap::value_parser!(u32).range(1..=3650))]
+        days: u32,
+    },
+    /// Run the care routine from the config, skipping any step that can't be done right now
+    Routine {
+        /// Name of the Nybbler
+        name: String,
+    },
+    /// Check that a save file is valid without importing it
+    Validate {
+        /// Path to the save file
+        path: PathBuf,
+    },
+    /// Print the JSON schema of the save file format
+    Schema,
+    /// Print the directory where Nybblers are saved
+    Where,
+    /// Summarize the whole collection of saved Nybblers
+    Household,
+    /// Update and save every Nybbler at once
+    UpdateAll,
+    /// Show how a saved Nybbler's mood has changed over time
+    #[command(visible_alias = "history")]
+    MoodHistory {
+        /// Name of the Nybbler
+        name: String,
+    },
+    /// Read a saved Nybbler's diary, written from its recorded moods and actions
+    Diary {
+        /// Name of the Nybbler
+        name: String,
+    },
+    /// Replay a saved Nybbler's recorded history as an animation
+    Timelapse {
+        /// Name of the Nybbler
+        name: String,
+    },
+    /// Print a shareable ASCII card for a saved Nybbler
+    Card {
+        /// Name of the Nybbler
+        name: String,
+    },
+    /// Save a picture of a Nybbler's art and stats as a PNG
+    #[cfg(feature = "image")]
+    Snapshot {
+        /// Name of the Nybbler
+        name: String,
+        /// Where to write the image
+        #[arg(long, default_value = "pet.png")]
+        out: PathBuf,
+    },
+    /// Serve each Nybbler's up-to-date save as JSON at http://HOST:PORT/<name>
+    #[cfg(feature = "server")]
+    Serve {
+        /// Port to listen on
+        #[arg(long, default_value_t = 8080)]
+        port: u16,
+        /// Address to listen on; use 0.0.0.0 to allow other machines
+        #[arg(long, default_value = "127.0.0.1")]
+        host: String,
+    },
+}
+
+// How good a stat level is, kept apart from the words so they can be flipped
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Level {
+    CriticallyLow,
+    Low,
+    Okay,
+    Good,
+}
+
+impl Level {
+    // Rate a stat value, with a critical one always counting as critically low
+    fn of(value: u8, critical: bool) -> Self {
+        match value {
+            _ if critical => Level::CriticallyLow,
+            70.. => Level::Good,
+            40..=69 => Level::Okay,
+            _ => Level::Low,
+        }
+    }
+
+    // Put the level into words, reading them the other way round for an inverted value
+    fn describe(self, inverted: bool) -> &'static str {
+        match (self, inverted) {
+            (Level::CriticallyLow, false) => "critically low",
+            (Level::CriticallyLow, true) => "critically high",
+            (Level::Low, false) => "low",
+            (Level::Low, true) => "high",
+            (Level::Okay, _) => "okay",
+            (Level::Good, false) => "good",
+            (Level::Good, true) => "low",
+        }
+    }
+}
+
+// Describe the Nybbler in plain sentences for text-to-speech
+fn display_stats_accessible(nybbler: &Nybbler, render: Render) {
+    println!();
+    println!(
+        "{} is {} days old, a {:?}. {} is {}.",
+        nybbler.name,
+        nybbler.age,
+        nybbler.life_stage(),
+        nybbler.name,
+        nybbler.mood.describe()
+    );
+    let critical = nybbler.critical_stats();
+    for stat in Stat::ALL {
+        let level = Level::of(nybbler.stat(stat), critical.contains(&stat));
+        let level = if stat == Stat::Hunger {
+            render.hunger.describe(level)
+        } else {
+            level.describe(false)
+        };
+        let value = nybbler.shown_stat(stat, render);
+        println!("{} is {} out of 100, which is {}.", stat.label(render), value, level);
+    }
+    if !nybbler.traits().is_empty() {
+        let traits: Vec<String> =
+            nybbler.traits().iter().map(|personality| format!("{:?}", personality).to_lowercase()).collect();
+        println!("{} is {}.", nybbler.name, join_clauses(&traits));
+    }
+    if let Some(trend) = nybbler.wellness_trend() {
+        println!("{}'s overall wellness is {}.", nybbler.name, trend.describe());
+    }
+    if let Some(day) = DailyMood::for_day(&nybbler.name, Local::now().date_naive()).describe() {
+        println!("{} {} today.", nybbler.name, day);
+    }
+    println!("{} loves {} right now.", nybbler.name, nybbler.favorite_activity().describe());
+    println!(
+        "{} is level {}, with {} of {} experience toward the next level.",
+        nybbler.name,
+        nybbler.level,
+        nybbler.xp,
+        nybbler.xp_to_next_level()
+    );
+    println!("You and {} {}, with a bond of {} out of 100.", nybbler.name, nybbler.bond_tier().describe(), nybbler.bond);
+    if !nybbler.notes.trim().is_empty() {
+        println!("Your notes say: {}", nybbler.notes.trim());
+    }
+    println!();
+}
+
+// List the stats as a compact table of numbers, flagging critical ones
+fn numeric_stats(nybbler: &Nybbler, render: Render) -> String {
+    let critical = nybbler.critical_stats();
+    Stat::ALL
+        .iter()
+        .map(|&stat| {
+            let line = format!("{:<width$} {:>3}/100", stat.label(render), nybbler.shown_stat(stat, render), width = Stat::label_width(render));
+            if critical.contains(&stat) {
+                format!("{} {}", style(line).bold().red(), render.pick("⚠️", "!!"))
+            } else {
+                line
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// The second heartbeat frame: the same art nudged one column right
+fn pulse_frame(art: &str) -> String {
+    art.lines().map(|line| if line.is_empty() { "\n".to_string() } else { format!(" {}\n", line) }).collect()
+}
+
+// Draw the stats as side-by-side columns with their values and names beneath
+fn vertical_stat_bars(nybbler: &Nybbler, render: Render) -> String {
+    const ROWS: u8 = 10;
+    const WIDTH: usize = 11;
+    let stats = [
+        (Stat::Hunger, Color::Blue),
+        (Stat::Happiness, Color::Magenta),
+        (Stat::Energy, Color::Yellow),
+        (Stat::Health, Color::Red),
+        (Stat::Cleanliness, Color::Cyan),
+    ];
+    let critical = nybbler.critical_stats();
+    let block = render.pick("███", "###");
+
+    let mut lines = Vec::new();
+    for row in (0..ROWS).rev() {
+        // Each row stands for ten points, and any part of them fills the cell
+        let cells: Vec<String> = stats
+            .iter()
+            .map(|&(stat, color)| {
+                let cell = if nybbler.shown_stat(stat, render) > row * 10 { block } else { "" };
+                style(format!("{:^WIDTH$}", cell)).fg(color).to_string()
+            })
+            .collect();
+        lines.push(cells.join(" "));
+    }
+
+    let values: Vec<String> = stats.iter().map(|&(stat, _)| format!("{:^WIDTH$}", nybbler.shown_stat(stat, render))).collect();
+    lines.push(values.join(" "));
+
+    let labels: Vec<String> = stats
+        .iter()
+        .map(|&(stat, color)| {
+            let label = style(format!("{:^WIDTH$}", stat.label(render))).bold();
+            if critical.contains(&stat) {
+                label.red().to_string()
+            } else {
+                label.fg(color).to_string()
+            }
+        })
+        .collect();
+    lines.push(labels.join(" "));
+
+    lines.join("\n")
+}
+
+fn display_stats(nybbler: &Nybbler, term: &Term, render: Render) -> Result<(), std::io::Error> {
+    if render.accessible {
+        display_stats_accessible(nybbler, render);
+        return Ok(());
+    }
+
+    term.clear_screen()?;
+
+    // Display fancy header with border
+    // The name keeps its own color inside the header
+    let age = format!("{}, {:?}", render.age_units.format(nybbler.age), nybbler.life_stage());
+    let (before, mut after) = if render.ascii {
+        ("* ", format!(" the Nybbler *  Age: {}", age))
+    } else {
+        ("✨ ", format!(" the Nybbler ✨  Age: {} 🎂", age))
+    };
+    // Show at a glance whether recent care is helping
+    if let Some(trend) = nybbler.wellness_trend() {
+        after.push_str(&format!("  {}", trend.arrow(render)));
+    }
+    let border = render.pick(
+        "•*´¨`*•.¸¸.•*´¨`*•.¸¸.•*´¨`*•.¸¸.•*´¨`*•.¸¸.•",
+        "*~*~*~*~*~*~*~*~*~*~*~*~*~*~*~*~*~*~*~*~*~*~*",
+    );
+
+    println!("{}", style(border).cyan());
+    println!("{}{}{}", style(before).bold().magenta(), nybbler.styled_name(), style(after).bold().magenta());
+    println!("{}", style(border).cyan());
+
+    // Display animated mood
+    println!("{} {}", style(nybbler.mood.glyph(render)).bold(), style(nybbler.mood.message(render)).italic());
+
+    // Personality traits, which pets saved before they existed lack
+    if !nybbler.traits().is_empty() {
+        let traits: Vec<String> = nybbler.traits().iter().map(|personality| format!("{:?}", personality)).collect();
+        println!("{}", style(render.labeled("🧬", &format!("Personality: {}", traits.join(", ")))).dim());
+    }
+
+    // Mention days that are going unusually well or badly
+    if let Some(day) = DailyMood::for_day(&nybbler.name, Local::now().date_naive()).describe() {
+        println!("{}", style(render.labeled("🌤️", &format!("{} {} today", nybbler.display_name(), day))).dim());
+    }
+
+    // Hint at what the Nybbler enjoys most right now
+    let favorite = format!("{} loves {} right now", nybbler.display_name(), nybbler.favorite_activity().describe());
+    println!("{}", style(render.labeled("💡", &favorite)).dim());
+
+    // How far the Nybbler has come from all the care it's had
+    println!("{}", style(level_line(nybbler, render)).cyan());
+    let bond = format!("Bond: {:?} ({}/100)", nybbler.bond_tier(), nybbler.bond);
+    println!("{}", style(render.labeled("💞", &bond)).magenta());
+
+    // List when the Nybbler reached each life stage
+    if !nybbler.milestones.is_empty() {
+        let grew: Vec<String> = nybbler
+            .milestones
+            .iter()
+            .map(|(stage, at)| format!("{:?} on {}", stage, at.format("%Y-%m-%d")))
+            .collect();
+        println!("{}", style(render.labeled("🎉", &format!("Grew up: {}", grew.join(", ")))).dim());
+    }
+
+    // Show the start of the player's notes without breaking the layout
+    if let Some(notes) = nybbler.notes_preview(NOTES_PREVIEW_WIDTH) {
+        println!("{} {}", render.pick("📝", "Notes:"), style(notes).dim());
+    }
+
+    // Display the pixelated character
+    let character_display = nybbler.mood_art(render);
+    println!("{}", style(&character_display).bold().yellow());
+
+    // A dying Nybbler's art pulses to draw the eye, but only on a real terminal
+    if nybbler.shows_heartbeat(render) && term.is_term() {
+        let pulse = pulse_frame(&character_display);
+        let height = character_display.lines().count() + 1;
+        for _ in 0..HEARTBEAT_BEATS {
+            thread::sleep(Duration::from_millis(HEARTBEAT_MILLIS));
+            term.clear_last_lines(height)?;
+            println!("{}", style(&pulse).bold().red());
+            thread::sleep(Duration::from_millis(HEARTBEAT_MILLIS));
+            term.clear_last_lines(height)?;
+            println!("{}", style(&character_display).bold().yellow());
+        }
+    }
+
+    println!();
+
+    // Some players would rather read plain numbers than bars
+    if render.numeric {
+        println!("{}", numeric_stats(nybbler, render));
+        println!();
+        return Ok(());
+    }
+
+    // Narrow terminals can show the stats as columns instead
+    if render.bars == BarLayout::Vertical {
+        println!("{}", vertical_stat_bars(nybbler, render));
+        println!();
+        return Ok(());
+    }
+
+    // Display stats bars with cute emojis
+    let get_bar_style = |emoji: &str| {
+        let prefix = if render.ascii { String::new() } else { format!("{}  ", emoji) };
+        ProgressStyle::with_template(&format!("{}[{{bar:20.green/red}}] {{pos}}/{{len}}", prefix))
+            .unwrap()
+            .progress_chars(render.pick("█▉▊▋▌▍▎▏ ", "#-"))
+    };
+
+    // Critical stats get a warning sign and a red label
+    let critical = nybbler.critical_stats();
+    let stat_label = |stat: Stat, color: Color| {
+        let name = stat.label(render);
+        if critical.contains(&stat) {
+            format!("{} {}", render.pick("⚠️", "!!"), style(name).bold().red())
+        } else {
+            style(name).bold().fg(color).to_string()
+        }
+    };
+
+    // Hunger
+    let hunger_bar = ProgressBar::new(100);
+    hunger_bar.set_style(get_bar_style("🍔"));
+    hunger_bar.set_position(nybbler.shown_stat(Stat::Hunger, render) as u64);
+    println!("{}:", stat_label(Stat::Hunger, Color::Blue));
+    hunger_bar.tick();
+
+    // Happiness
+    let happiness_bar = ProgressBar::new(100);
+    happiness_bar.set_style(get_bar_style("🎈"));
+    happiness_bar.set_position(nybbler.happiness as u64);
+    println!("{}:", stat_label(Stat::Happiness, Color::Magenta));
+    happiness_bar.tick();
+
+    // Energy
+    let energy_bar = ProgressBar::new(100);
+    energy_bar.set_style(get_bar_style("⚡"));
+    energy_bar.set_position(nybbler.energy as u64);
+    println!("{}:", stat_label(Stat::Energy, Color::Yellow));
+    energy_bar.tick();
+
+    // Health
+    let health_bar = ProgressBar::new(100);
+    health_bar.set_style(get_bar_style("💖"));
+    health_bar.set_position(nybbler.health as u64);
+    println!("{}:", stat_label(Stat::Health, Color::Red));
+    health_bar.tick();
+
+    // Cleanliness
+    let cleanliness_bar = ProgressBar::new(100);
+    cleanliness_bar.set_style(get_bar_style("🧼"));
+    cleanliness_bar.set_position(nybbler.cleanliness as u64);
+    println!("{}:", stat_label(Stat::Cleanliness, Color::Cyan));
+    cleanliness_bar.tick();
+
+    println!();
+    Ok(())
+}
+
+// Refresh the stats every `interval` until a key is pressed or the Nybbler dies
+fn watch_until_key(nybbler: &mut Nybbler, term: &Term, ctx: &GameContext, interval: Duration) -> io::Result<()> {
+    // Read the key on another thread so the refresh timer keeps running;
+    // the thread ends after one key so it never competes with the menu
+    let (sender, receiver) = mpsc::channel();
+    let key_term = term.clone();
+    thread::spawn(move || {
+        let _ = sender.send(key_term.read_key());
+    });
+
+    loop {
+        let hint = ctx.render.labeled("👀", "Watching... press any key to bring up the menu");
+        println!("{}", style(hint).dim().italic());
+        match receiver.recv_timeout(interval) {
+            Err(RecvTimeoutError::Timeout) => {
+                nybbler.update(&ctx.config);
+                if !nybbler.is_alive() {
+                    return Ok(());
+                }
+                display_stats(nybbler, term, ctx.render)?;
+            },
+            Ok(_) | Err(RecvTimeoutError::Disconnected) => {
+                term.clear_last_lines(1)?;
+                return Ok(());
+            },
+        }
+    }
+}
+
+// Ask whether the player wants to type a name (0) or roll a random one (1)
+fn pick_name(ctx: &GameContext, term: &Term) -> io::Result<usize> {
+    let name_options = [
+        ctx.render.labeled("✏️", "Type a name (new or existing)"),
+        ctx.render.labeled("🎲", "Random name"),
+    ];
+    Select::with_theme(&ColorfulTheme::default())
+        .with_prompt("How would you like to pick your Nybbler?")
+        .items(&name_options)
+        .default(0)
+        .interact_on(term)
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Parse command line arguments
+    let cli = Cli::parse();
+
+    // A name given on the command line can't also pick a subcommand
+    if let (Some(pet), Some(_)) = (&cli.pet, &cli.command) {
+        eprintln!("Give either a pet's name or a command, not both (got {}).", pet);
+        process::exit(1);
+    }
+
+    // Script-friendly status output keeps one format, so a display layout can't apply to it
+    let prints_status = matches!(cli.command, Some(Commands::Stats { .. } | Commands::Status { .. } | Commands::FastForward { .. }));
+    if prints_status && (cli.numeric || cli.bars != BarLayout::Horizontal) {
+        eprintln!("--numeric and --bars don't apply to `stats`, `status`, or `fast-forward`, which always print plain numbers.");
+        process::exit(1);
+    }
+
+    // Decide between emoji-rich and plain ASCII output
+    let mut render = Render::new(cli.ascii, cli.unicode, cli.accessible, cli.bars);
+    let config = Config::load();
+    render.hunger = config.hunger_display;
+    render.age_units = config.age_units;
+    render.numeric = cli.numeric;
+
+    // The diagnostic log can be turned on per run or for good in the config
+    if cli.log || config.log_events {
+        eventlog::enable();
+    }
+
+    // Older versions could leave saves with capitals in their names
+    if let Ok(save_dir) = resolve_save_directory() {
+        match normalize_save_file_names(&save_dir) {
+            Ok(renamed) => {
+                for name in renamed {
+                    eprintln!("Renamed {} to {} so it works the same on every system.", name, name.to_lowercase());
+                }
+            },
+            Err(e) => eprintln!("Could not rename saves to lowercase: {}", e),
+        }
+    }
+
+    // Handle commands
+    match cli.command {
+        Some(Commands::New { name, character, start, force }) => {
+            if let Err(e) = validate_name(&name) {
+                eprintln!("{}", e);
+                process::exit(1);
+            }
+            if Nybbler::save_exists(&name) && !force {
+                eprintln!("A Nybbler named {} already exists. Pass --force to replace it.", name.trim());
+                process::exit(1);
+            }
+
+            let mut ctx = GameContext::new(cli.seed, config, render);
+            if !Nybbler::save_exists(&name) && !make_room_for_new_pet(&ctx.config, ctx.render, cli.yes)? {
+                process::exit(1);
+            }
+            let profile = start.unwrap_or(ctx.config.starting_profile);
+            let mut nybbler = Nybbler::new(name.trim().to_string(), profile, &mut ctx.rng);
+            if let Some(character) = character {
+                nybbler.character_type = character;
+            }
+
+            if let Err(e) = nybbler.save() {
+                eprintln!("Error saving {}: {}", nybbler.name, e);
+                process::exit(1);
+            }
+            let created = format!("Created {} the {:?}!", nybbler.name, nybbler.character_type);
+            println!("{}", render.labeled("🐣", &created));
+            return Ok(());
+        },
+        Some(Commands::DeleteAll) => match delete_all_nybblers() {
+            Ok(count) => {
+                println!("{}", render.labeled("🗑️", &format!("Successfully deleted {} Nybbler pets!", count)));
+                println!("{}", render.labeled("🎮", "Run the game without arguments to create a new pet."));
+                return Ok(());
+            },
+            Err(e) => {
+                eprintln!("Error deleting Nybblers: {}", e);
+                process::exit(1);
+            }
+        },
+        Some(Commands::Abandon { name }) => {
+            if !Nybbler::save_exists(&name) {
+                report_missing_pet(&name);
+                process::exit(EXIT_NOT_FOUND);
+            }
+            let save_dir = get_save_directory()?;
+            match move_save(&save_dir, &save_dir.join(ARCHIVE_DIR), &name) {
+                Ok(()) => {
+                    let archived = format!("{} has been archived. Run `nybbler unarchive {}` to bring them back.", name.trim(), name.trim());
+                    println!("{}", render.labeled("📦", &archived));
+                    return Ok(());
+                },
+                Err(e) => {
+                    eprintln!("Error archiving {}: {}", name.trim(), e);
+                    process::exit(1);
+                }
+            }
+        },
+        Some(Commands::Rename { old, new }) => {
+            match rename_save(&get_save_directory()?, &old, &new) {
+                Ok(nybbler) => {
+                    println!("{}", render.labeled("🏷️", &format!("{} is now called {}!", old.trim(), nybbler.name)));
+                    return Ok(());
+                },
+                Err(e) => exit_with_save_error(&old, "renaming", e),
+            }
+        },
+        Some(Commands::Unarchive { name }) => {
+            let save_dir = get_save_directory()?;
+            match move_save(&save_dir.join(ARCHIVE_DIR), &save_dir, &name) {
+                Ok(()) => {
+                    println!("{}", render.labeled("🏠", &format!("{} is back home!", name.trim())));
+                    return Ok(());
+                },
+                Err(e) if e.kind() == ErrorKind::NotFound => {
+                    eprintln!("No archived Nybbler named {} was found.", name.trim());
+                    process::exit(EXIT_NOT_FOUND);
+                },
+                Err(e) => {
+                    eprintln!("Error restoring {}: {}", name.trim(), e);
+                    process::exit(1);
+                }
+            }
+        },
+        Some(Commands::Dedupe) => match dedupe_nybblers(render) {
+            Ok(count) => {
+                println!("{}", render.labeled("🧹", &format!("Removed {} duplicate saves and normalized file names!", count)));
+                return Ok(());
+            },
+            Err(e) => {
+                eprintln!("Error deduplicating Nybblers: {}", e);
+                process::exit(1);
+            }
+        },
+        Some(Commands::ImportDir { path }) => match import_directory(&path, render, cli.yes) {
+            Ok((imported, skipped, failed)) => {
+                println!("{}", render.labeled("📦", &format!("Imported {}, skipped {}, failed {}.", imported, skipped, failed)));
+                return Ok(());
+            },
+            Err(e) => {
+                eprintln!("Error importing from {}: {}", path.display(), e);
+                process::exit(1);
+            }
+        },
+        Some(Commands::Backup { out }) => match list_save_files().and_then(|paths| write_backup(&paths, &out)) {
+            Ok((saved, skipped)) => {
+                let mut message = format!("Backed up {} Nybblers to {}", saved, out.display());
+                if skipped > 0 {
+                    message.push_str(&format!(", skipping {} unreadable saves", skipped));
+                }
+                println!("{}", render.labeled("💾", &message));
+                return Ok(());
+            },
+            Err(e) => {
+                eprintln!("Error writing backup to {}: {}", out.display(), e);
+                process::exit(1);
+            }
+        },
+        Some(Commands::Restore { path }) => {
+            let entries = match read_backup(&path) {
+                Ok(entries) => entries,
+                Err(e) => {
+                    eprintln!("Error reading backup {}: {}", path.display(), e);
+                    process::exit(1);
+                }
+            };
+            let mut counts = (0, 0, 0);
+            for (i, pet) in entries.into_iter().enumerate() {
+                import_pet(pet, &format!("{} entry {}", path.display(), i + 1), render, cli.yes, &mut counts)?;
+            }
+            let (restored, skipped, failed) = counts;
+            println!("{}", render.labeled("📦", &format!("Restored {}, skipped {}, failed {}.", restored, skipped, failed)));
+            return Ok(());
+        },
+        Some(Commands::List) => {
+            if let Err(e) = print_list(render) {
+                eprintln!("Error listing Nybblers: {}", e);
+                process::exit(1);
+            }
+            return Ok(());
+        },
+        Some(Commands::Ensure { name }) => {
+            if let Err(e) = validate_name(&name) {
+                eprintln!("{}", e);
+                process::exit(1);
+            }
+            let mut ctx = GameContext::new(cli.seed, config, render);
+            let created = !Nybbler::save_exists(&name);
+            if created && !make_room_for_new_pet(&ctx.config, ctx.render, cli.yes)? {
+                process::exit(1);
+            }
+            // Unlike the interactive start, nothing here ever prompts
+            let result = if created {
+                let nybbler = Nybbler::new(name.trim().to_string(), ctx.config.starting_profile, &mut ctx.rng);
+                nybbler.save().map(|()| nybbler)
+            } else {
+                Nybbler::load(&name).and_then(|mut nybbler| {
+                    nybbler.update(&ctx.config);
+                    nybbler.save().map(|()| nybbler)
+                })
+            };
+            match result {
+                Ok(nybbler) => {
+                    if created {
+                        println!("Created a new Nybbler named {}.", nybbler.name);
+                    } else {
+                        println!("Loaded {}, who already existed.", nybbler.name);
+                    }
+                    println!("{}", render_status(&nybbler));
+                    return Ok(());
+                },
+                Err(e) => exit_with_save_error(&name, "loading or creating", e),
+            }
+        },
+        Some(Commands::Favorite { name }) => {
+            match list_save_files().and_then(|paths| set_favorite(&paths, &name)) {
+                Ok(true) => {
+                    println!("{}", render.labeled("⭐", &format!("{} is now your favorite!", name.trim())));
+                    return Ok(());
+                },
+                Ok(false) => {
+                    report_missing_pet(&name);
+                    process::exit(EXIT_NOT_FOUND);
+                },
+                Err(e) => {
+                    eprintln!("Error setting {} as favorite: {}", name.trim(), e);
+                    process::exit(1);
+                }
+            }
+        },
+        Some(Commands::Stats { name }) => {
+            match Nybbler::read(&name) {
+                Ok(nybbler) => {
+                    println!("{}", render_status(&nybbler));
+                    return Ok(());
+                },
+                Err(e) => exit_with_save_error(&name, "loading", e),
+            }
+        },
+        Some(Commands::Status { name }) => {
+            match get_save_directory().map_err(SaveError::from).and_then(|dir| refresh_status(&dir, &name, &config)) {
+                Ok(status) => {
+                    println!("{}", status);
+                    return Ok(());
+                },
+                Err(e) => exit_with_save_error(&name, "updating", e),
+            }
+        },
+        Some(Commands::SetAlert { name, threshold }) => {
+            let result = Nybbler::load(&name).and_then(|mut nybbler| {
+                nybbler.alert_threshold = threshold;
+                nybbler.save().map(|()| nybbler)
+            });
+            match result {
+                Ok(nybbler) => {
+                    let flagged = format!("{}'s stats will now be flagged below {}.", nybbler.name, threshold);
+                    println!("{}", render.labeled("🚨", &flagged));
+                    return Ok(());
+                },
+                Err(e) => exit_with_save_error(&name, "updating", e),
+            }
+        },
+        Some(Commands::Routine { name }) => {
+            let mut ctx = GameContext::new(cli.seed, config, render);
+            let mut nybbler = Nybbler::load(&name).unwrap_or_else(|e| exit_with_save_error(&name, "loading", e));
+            nybbler.update(&ctx.config);
+            if !nybbler.is_alive() {
+                nybbler.save().unwrap_or_else(|e| exit_with_save_error(&name, "saving", e));
+                eprintln!("{} has passed away and can't be cared for anymore.", nybbler.name);
+                process::exit(1);
+            }
+
+            let routine = ctx.config.routine.clone();
+            let steps = run_routine(&mut nybbler, &routine, &mut ctx.rng, render);
+            nybbler.save().unwrap_or_else(|e| exit_with_save_error(&name, "saving", e));
+
+            println!("{}", render.labeled("📋", &format!("Ran {}'s routine:", nybbler.name)));
+            for step in &steps {
+                println!("{}", step.describe(render, &ctx.config));
+            }
+            let ran = steps.iter().filter(|step| matches!(step, RoutineStep::Ran(..))).count();
+            println!("{} of {} steps done.", ran, steps.len());
+            return Ok(());
+        },
+        Some(Commands::FastForward { name, days }) => {
+            if !Nybbler::save_exists(&name) {
+                report_missing_pet(&name);
+                process::exit(EXIT_NOT_FOUND);
+            }
+
+            // There's no undo, so make sure the player means it
+            if !cli.yes {
+                require_terminal();
+                let warning = format!(
+                    "This permanently ages {} by {} days without any care and may kill them.",
+                    name.trim(),
+                    days
+                );
+                eprintln!("{}", render.labeled("⚠️", &warning));
+                let proceed = dialoguer::Confirm::new()
+                    .with_prompt("Fast-forward anyway?")
+                    .default(false)
+                    .interact()?;
+                if !proceed {
+                    println!("Nothing was changed.");
+                    return Ok(());
+                }
+            }
+
+            let result = Nybbler::load(&name).and_then(|mut nybbler| {
+                nybbler.fast_forward(days, &config);
+                nybbler.save().map(|()| nybbler)
+            });
+            match result {
+                Ok(nybbler) if nybbler.is_alive() => {
+                    println!("{}", render.labeled("⏩", &format!("{} days later...", days)));
+                    println!("{}", render_status(&nybbler));
+                    return Ok(());
+                },
+                Ok(nybbler) => {
+                    let message = format!("{} didn't survive the {} days, passing away at {} days old.", nybbler.name, days, nybbler.age);
+                    println!("{}", render.labeled("💔", &message));
+                    return Ok(());
+                },
+                Err(e) => exit_with_save_error(&name, "fast-forwarding", e),
+            }
+        },
+        Some(Commands::Validate { path }) => match inspect_save(&path) {
+            Ok((nybbler, warnings)) => {
+                if warnings.is_empty() {
+                    println!("{}", render.labeled("✅", &format!("{} is a valid Nybbler save.", path.display())));
+                } else {
+                    println!("{}", render.labeled("⚠️", &format!("{} can be imported, but:", path.display())));
+                    for warning in &warnings {
+                        println!("  - {}", warning);
+                    }
+                }
+                println!(
+                    "{} the {:?}, {} days old ({:?}), {}",
+                    nybbler.name,
+                    nybbler.character_type,
+                    nybbler.age,
+                    nybbler.life_stage(),
+                    if nybbler.is_alive() { "alive" } else { "no longer alive" }
+                );
+                println!("{}", render_status(&nybbler));
+                return Ok(());
+            },
+            Err(e) => {
+                eprintln!("{}", render.labeled("❌", &format!("{} is not a valid Nybbler save: {}", path.display(), e)));
+                process::exit(1);
+            }
+        },
+        Some(Commands::Schema) => {
+            let schema = schemars::schema_for!(Nybbler);
+            println!("{}", serde_json::to_string_pretty(&schema)?);
+            return Ok(());
+        },
+        Some(Commands::Where) => {
+            if let Err(e) = print_save_directory() {
+                eprintln!("Error finding the save directory: {}", e);
+                process::exit(1);
+            }
+            return Ok(());
+        },
+        Some(Commands::UpdateAll) => {
+            if let Err(e) = update_all(&mut GameContext::new(cli.seed, config, render)) {
+                eprintln!("Error updating Nybblers: {}", e);
+                process::exit(1);
+            }
+            return Ok(());
+        },
+        Some(Commands::Household) => {
+            if let Err(e) = print_household(&config, render) {
+                eprintln!("Error reading household: {}", e);
+                process::exit(1);
+            }
+            return Ok(());
+        },
+        Some(Commands::MoodHistory { name }) => match Nybbler::read(&name) {
+            Ok(nybbler) => {
+                if nybbler.mood_history.is_empty() {
+                    println!("{}", render.labeled("📖", &format!("{} hasn't had any mood changes recorded yet.", nybbler.name)));
+                }
+                for entry in &nybbler.mood_history {
+                    println!("{}  {} {:?}", entry.at.format("%Y-%m-%d %H:%M"), entry.mood.glyph(render), entry.mood);
+                }
+                return Ok(());
+            },
+            Err(e) => exit_with_save_error(&name, "loading", e),
+        },
+        Some(Commands::Diary { name }) => match Nybbler::read(&name) {
+            Ok(nybbler) => {
+                let entries = diary_entries(&nybbler);
+                if entries.is_empty() {
+                    let message = format!("{}'s diary is empty so far. Check back after some adventures!", nybbler.name);
+                    println!("{}", render.labeled("📔", &message));
+                } else {
+                    println!("{}", render.labeled("📔", &format!("{}'s diary", nybbler.name)));
+                }
+                for entry in entries {
+                    println!("{}", entry);
+                }
+                return Ok(());
+            },
+            Err(e) => exit_with_save_error(&name, "loading", e),
+        },
+        Some(Commands::Timelapse { name }) => match Nybbler::read(&name) {
+            Ok(nybbler) => {
+                play_timelapse(&nybbler, &Term::stdout(), render)?;
+                return Ok(());
+            },
+            Err(e) => exit_with_save_error(&name, "loading", e),
+        },
+        Some(Commands::Export { name, output, anonymize }) => {
+            // Exports are copies, so nothing here touches the save itself
+            let result = Nybbler::load(&name).and_then(|mut nybbler| {
+                if anonymize {
+                    nybbler.anonymize();
+                }
+                match &output {
+                    Some(path) => nybbler.save_path(path),
+                    None => serde_json::to_string_pretty(&nybbler)
+                        .map(|json| println!("{}", json))
+                        .map_err(|e| io::Error::other(e).into()),
+                }
+            });
+            match result {
+                Ok(()) => {
+                    if let Some(path) = output {
+                        println!("{}", render.labeled("📦", &format!("Exported {} to {}", name, path.display())));
+                    }
+                    return Ok(());
+                },
+                Err(e) => exit_with_save_error(&name, "exporting", e),
+            }
+        },
+        Some(Commands::Import { name, force }) => {
+            let data = io::read_to_string(io::stdin())?;
+            let nybbler = parse_import(&data, &name).unwrap_or_else(|e| {
+                eprintln!("Could not import {}: {}", name.trim(), e);
+                process::exit(1);
+            });
+            if Nybbler::save_exists(&nybbler.name) && !force {
+                eprintln!("A Nybbler named {} already exists. Pass --force to replace it.", nybbler.name);
+                process::exit(1);
+            }
+            nybbler.save().unwrap_or_else(|e| exit_with_save_error(&name, "importing", e));
+            println!("{}", render.labeled("✅", &format!("Imported {}", nybbler.name)));
+            return Ok(());
+        },
+        Some(Commands::Card { name }) => match Nybbler::read(&name) {
+            // The card reflects the saved state, so no update() here
+            Ok(nybbler) => {
+                println!("{}", render_card(&nybbler, render));
+                return Ok(());
+            },
+            Err(e) => exit_with_save_error(&name, "loading", e),
+        },
+        #[cfg(feature = "image")]
+        Some(Commands::Snapshot { name, out }) => {
+            // Like the card, the photo shows the saved state without updating it
+            let nybbler = Nybbler::read(&name).unwrap_or_else(|e| exit_with_save_error(&name, "loading", e));
+            if let Err(e) = photo::save_photo(&nybbler, render, &out) {
+                eprintln!("Error writing {}: {}", out.display(), e);
+                process::exit(1);
+            }
+            println!("{}", render.labeled("📸", &format!("Saved a photo of {} to {}", nybbler.name, out.display())));
+            return Ok(());
+        },
+        #[cfg(feature = "server")]
+        Some(Commands::Serve { port, host }) => {
+            if let Err(e) = server::serve(&host, port) {
+                eprintln!("Error serving on {}:{}: {}", host, port, e);
+                process::exit(1);
+            }
+            return Ok(());
+        },
+        None => {}
+    }
+
+    // Scripts get one fresh update without any prompts or animations
+    if cli.once {
+        let name = match cli.name {
+            Some(name) => name,
+            None => match last_played_name()? {
+                Some(name) => name,
+                None => {
+                    eprintln!("{}", NO_PETS_HINT);
+                    process::exit(EXIT_NOT_FOUND);
+                }
+            },
+        };
+        match get_save_directory().map_err(SaveError::from).and_then(|dir| refresh_status(&dir, &name, &config)) {
+            Ok(status) => {
+                println!("{}", status);
+                return Ok(());
+            },
+            Err(e) => exit_with_save_error(&name, "updating", e),
+        }
+    }
+
+    // Regular game flow
+    require_terminal();
+    let mut ctx = GameContext::new(cli.seed, config, render);
+    let term = Term::stdout();
+    term.clear_screen()?;
+
+    // Welcome message with cute ASCII art
+    println!("{}", style(format!("
+     /\\_/\\
+    ( o.o )
+     > ^ <
+{}", ctx.render.pick("  ✨ NYBBLER ✨", "     NYBBLER"))).bold().yellow());
+    println!("{}", style("Welcome to Terminal Nybbler!").bold().green());
+    println!("{}", style(ctx.render.framed("🌈", "Take care of your virtual pet and keep it happy!")).cyan());
+    println!("{}", style(ctx.render.framed("✨", "You can create a new pet or load an existing one by name!")).italic().magenta());
+    println!();
+
+    // A name on the command line skips straight to loading or creating that pet
+    let named = cli.pet.is_some();
+    let name = if let Some(name) = cli.pet {
+        let name = name.trim().to_string();
+        if let Err(e) = validate_name(&name) {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+        name
+    } else if pick_name(&ctx, &term)? == 1 {
+        let name = random_name(&mut ctx.rng);
+        println!("{}", ctx.render.labeled("🎲", &format!("Say hello to {}!", style(&name).bold().yellow())));
+        name
+    } else {
+        // Ask for a name (or to load an existing Nybbler), re-prompting until it's usable
+        dialoguer::Input::<String>::new()
+            .with_prompt("Enter your Nybbler's name (new or existing)")
+            .validate_with(|input: &String| validate_name(input))
+            .interact_text()?
+            .trim()
+            .to_string()
+    };
+
+    // Hold the pet for the whole session, so other commands can't save over it
+    let mut _lock = PetLock::for_pet(&name).unwrap_or_else(|e| exit_with_save_error(&name, "opening", e));
+
+    // Check if a save exists and ask if we should load it, unless it was asked for by name
+    let mut nybbler = if Nybbler::save_exists(&name) {
+        let load_save = named
+            || dialoguer::Confirm::new()
+                .with_prompt(format!("A Nybbler named {} already exists! Would you like to load it?", name))
+                .default(true)
+                .interact()?;
+
+        if load_save {
+            match Nybbler::load(&name) {
+                Ok(mut loaded) => {
+                    println!("{}", ctx.render.framed("🎉", &format!("{} has been loaded!", style(&name).bold().yellow())));
+
+                    // Catch up right away so the player can see what changed while they were gone
+                    let (before, mood_before, since) = (loaded.shown_stats(ctx.render), loaded.mood, loaded.last_updated);
+                    loaded.update(&ctx.config);
+                    if let Some(recap) = away_recap(&loaded, before, mood_before, since, ctx.render) {
+                        println!("{}", ctx.render.labeled("⏰", &recap));
+                    }
+                    thread::sleep(Duration::from_millis(2500));
+                    loaded
+                },
+                // Keep the damaged file around so the new pet doesn't overwrite it
+                Err(SaveError::Parse(e)) => {
+                    println!("{}'s save is damaged: {}", name, e);
+                    let backup = get_save_directory().and_then(|dir| back_up_damaged_save(&dir.join(save_file_name(&name))));
+                    match backup {
+                        Ok(backup) => println!("The damaged save was kept as {}.", backup.display()),
+                        Err(e) => {
+                            eprintln!("Couldn't back up the damaged save, so nothing was changed: {}", e);
+                            process::exit(1);
+                        }
+                    }
+                    println!("Creating a new Nybbler instead...");
+                    thread::sleep(Duration::from_millis(1500));
+                    Nybbler::new(name, ctx.config.starting_profile, &mut ctx.rng)
+                },
+                Err(e) => exit_with_save_error(&name, "loading", e),
+            }
+        } else {
+            println!("Creating a new Nybbler named {}...", name);
+            Nybbler::new(name, ctx.config.starting_profile, &mut ctx.rng)
+        }
+    } else {
+        // Create new Nybbler, if the configured cap leaves room for one
+        if !make_room_for_new_pet(&ctx.config, ctx.render, cli.yes)? {
+            process::exit(1);
+        }
+        Nybbler::new(name, ctx.config.starting_profile, &mut ctx.rng)
+    };
+
+    // The full-screen view runs its own loop and saves on exit
+    if cli.tui {
+        tui::run(&mut nybbler, &mut ctx)?;
+        if nybbler.is_alive() {
+            println!("{}", ctx.render.framed("🌈", &format!("{} will be waiting for your return!", nybbler.display_name())));
+        } else {
+            println!("{}", ctx.render.framed("🌈", &format!("{} lived for {} wonderful days with you.", nybbler.display_name(), nybbler.age)));
+        }
+        return Ok(());
+    }
+
+    // Graduation is only offered once a session, so saying no sticks
+    let mut offered_graduation = false;
+
+    // Main game loop
+    loop {
+        // Update nybbler state
+        nybbler.update(&ctx.config);
+
+        // Celebrate each new life stage once, before the stats screen clears it
+        for stage in nybbler.take_milestone_announcements() {
+            let grew = format!("{} grew into {}!", nybbler.display_name(), stage.with_article());
+            println!("{}", style(ctx.render.labeled("🎉", &grew)).bold().green());
+            if stage == LifeStage::Adult {
+                let bonus = format!("{} is hardier now: hunger, happiness, and energy fall 20% slower.", nybbler.display_name());
+                println!("{}", style(ctx.render.labeled("💪", &bonus)).green());
+            }
+            thread::sleep(Duration::from_millis(1500));
+        }
+
+        // Check if nybbler is alive
+        if !nybbler.is_alive() {
+            term.clear_screen()?;
+            println!("{}", style("
+      .======.
+      | RIP |
+      |      |
+      |      |
+      |      |
+      '======'").bold());
+            println!("{}", style(ctx.render.framed("💔", "Oh no! Your Nybbler has passed away!")).bold().red());
+            println!("{}", ctx.render.framed("🌈", &format!("{} lived for {} wonderful days with you.", nybbler.display_name(), nybbler.age)));
+            println!("{}", ctx.render.framed("🌟", "Thank you for taking care of your Nybbler!"));
+            break;
+        }
+
+        // A well-loved Elder can move on happily instead of growing frail
+        if !offered_graduation && nybbler.can_graduate(Local::now()) {
+            offered_graduation = true;
+            if graduate(&nybbler, &term, ctx.render)? {
+                break;
+            }
+        }
+
+        // Display stats
+        display_stats(&nybbler, &term, ctx.render)?;
+
+        // In watch mode, keep the stats ticking until a key is pressed
+        if let Some(seconds) = cli.interval {
+            watch_until_key(&mut nybbler, &term, &ctx, Duration::from_secs(seconds.max(1)))?;
+            if !nybbler.is_alive() {
+                continue;
+            }
+        }
+
+        // Show available actions with cute emojis
+        let now = Local::now();
+        let actions = nybbler.available_actions(&ctx.config);
+        let mut options: Vec<String> = actions
+            .iter()
+            .map(|&action| match nybbler.try_action_at(action, now) {
+                Err(error) => format!("{} ({})", action.display_label(ctx.render, &ctx.config), error.short()),
+                Ok(()) => action.menu_label(ctx.render, &ctx.config),
+            })
+            .collect();
+        // The stat editor is only ever offered with --debug
+        if cli.debug {
+            options.push(ctx.render.labeled("🛠️", "[DEBUG] Set stats"));
+        }
+        let suggested = suggested_action(&nybbler, &actions, now);
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt(ctx.render.pick("✨ What would you like to do? ✨", "What would you like to do?"))
+            .items(&options)
+            .default(suggested)
+            .interact_on(&term)?;
+
+        if selection == actions.len() {
+            debug_set_stats(&mut nybbler, ctx.render)?;
+            nybbler.save()?;
+            continue;
+        }
+
+        // Refuse actions whose preconditions aren't met, saying why
+        let action = actions[selection];
+        if let Err(error) = nybbler.try_action(action) {
+            println!("{}", style(ctx.render.labeled("⏳", &error.reason(&nybbler.display_name()))).yellow());
+            thread::sleep(Duration::from_millis(1000));
+            continue;
+        }
+        nybbler.record(action);
+        let before = nybbler.shown_stats(ctx.render);
+        let level_before = nybbler.level;
+        let bond_before = nybbler.bond_tier();
+
+        // Process selection with cute responses
+        match action {
+            Action::Feed => {
+                nybbler.feed();
+                println!("{}", ctx.render.framed("🎉", &format!("You fed {} a delicious meal! {}Yum yum!", nybbler.styled_name(), ctx.render.pick("🍔 ", ""))));
+                for _ in 0..3 {
+                    thread::sleep(Duration::from_millis(300));
+                    term.clear_last_lines(1)?;
+                    println!("{}", ctx.render.framed("🍽️", &format!("Nom nom nom... {} is eating!", nybbler.styled_name())));
+                    println!("{}", style(ctx.render.art(nybbler.character_type.eating())).bold().yellow());
+                    thread::sleep(Duration::from_millis(300));
+                    term.clear_last_lines(2)?;
+                    println!("{}", ctx.render.framed("😋", nybbler.character_type.speak(Moment::Eating)));
+                    println!("{}", style(ctx.render.art(nybbler.character_type.neutral())).bold().yellow());
+                }
+            },
+            Action::Treat => {
+                let (gained, crashed) = nybbler.treat();
+                println!("{}", ctx.render.framed("🍬", &format!("You gave {} a sweet treat!", nybbler.styled_name())));
+                println!("{}", style(ctx.render.art(nybbler.character_type.eating())).bold().yellow());
+                if crashed {
+                    println!("{}", ctx.render.framed("🤢", &format!("Too much sugar! {} is having a sugar crash...", nybbler.styled_name())));
+                } else if gained < TREAT_HAPPINESS {
+                    println!("{}", style("The treats don't seem quite as exciting anymore...").dim().italic());
+                }
+            },
+            Action::Play => {
+                let toys: Vec<String> = Toy::ALL.iter().map(|toy| toy.menu_label(ctx.render)).collect();
+                let choice = Select::with_theme(&ColorfulTheme::default())
+                    .with_prompt(ctx.render.pick("🧸 What should you play with? 🧸", "What should you play with?"))
+                    .items(&toys)
+                    .default(0)
+                    .interact_on(&term)?;
+                let toy = Toy::ALL[choice];
+                nybbler.play(toy);
+                match toy.name() {
+                    Some(toy) => println!("{}", ctx.render.framed("🎮", &format!("You played with {} and the {}! So much fun!", nybbler.styled_name(), toy))),
+                    None => println!("{}", ctx.render.framed("🎮", &format!("You played with {}! So much fun!", nybbler.styled_name()))),
+                }
+                for _ in 0..3 {
+                    thread::sleep(Duration::from_millis(300));
+                    term.clear_last_lines(1)?;
+                    println!("{}", ctx.render.framed("🎯", &format!("Wheee! {} is having fun!", nybbler.styled_name())));
+                    println!("{}", style(ctx.render.art(nybbler.character_type.playing())).bold().yellow());
+                    thread::sleep(Duration::from_millis(300));
+                    term.clear_last_lines(2)?;
+                    println!("{}", ctx.render.framed("🏀", nybbler.character_type.speak(Moment::Playing)));
+                    println!("{}", style(ctx.render.art(nybbler.character_type.neutral())).bold().yellow());
+                }
+            },
+            Action::Sleep => {
+                nybbler.sleep();
+                println!("{}", ctx.render.framed("💤", &format!("{} took a nap and feels refreshed!", nybbler.styled_name())));
+                for _ in 0..3 {
+                    thread::sleep(Duration::from_millis(400));
+                    term.clear_last_lines(1)?;
+                    println!("{}", ctx.render.framed("😴", &format!("Zzz... {} is sleeping soundly...", nybbler.styled_name())));
+                    println!("{}", style(ctx.render.art(nybbler.character_type.sleeping())).bold().yellow());
+                    thread::sleep(Duration::from_millis(400));
+                    term.clear_last_lines(2)?;
+                    println!("{}", ctx.render.framed("💭", nybbler.character_type.speak(Moment::Sleeping)));
+                    println!("{}", style(ctx.render.art(nybbler.character_type.sleeping())).bold().yellow());
+                }
+            },
+            Action::Heal => {
+                nybbler.heal();
+                println!("{}", ctx.render.framed("💊", &format!("You gave {} medicine and they're feeling better!", nybbler.styled_name())));
+                for _ in 0..3 {
+                    thread::sleep(Duration::from_millis(300));
+                    term.clear_last_lines(1)?;
+                    println!("{}", ctx.render.framed("🌡️", &format!("{} is recovering...", nybbler.styled_name())));
+                    println!("{}", style(ctx.render.art(nybbler.character_type.healing())).bold().yellow());
+                    thread::sleep(Duration::from_millis(300));
+                    term.clear_last_lines(2)?;
+                    println!("{}", ctx.render.framed("💪", nybbler.character_type.speak(Moment::Healing)));
+                    println!("{}", style(ctx.render.art(nybbler.character_type.neutral())).bold().yellow());
+                }
+            },
+            Action::Clean => {
+                nybbler.clean();
+                println!("{}", ctx.render.framed("🧼", &format!("You gave {} a bubbly bath!", nybbler.styled_name())));
+                println!("{}", style(ctx.render.art(nybbler.character_type.neutral())).bold().yellow());
+                println!("{}", style(format!("{} is squeaky clean!", nybbler.display_name())).dim().italic());
+            },
+            Action::Comfort => {
+                if nybbler.mood == NybblerMood::Sick {
+                    println!("{}", ctx.render.framed("🫂", &format!("You tuck {} in with a warm blanket and stay close by.", nybbler.styled_name())));
+                } else {
+                    println!("{}", ctx.render.framed("🫂", &format!("You give {} a big, gentle hug.", nybbler.styled_name())));
+                }
+                println!("{}", style(ctx.render.art(nybbler.character_type.neutral())).bold().yellow());
+                if nybbler.comfort(&mut ctx.rng) {
+                    println!("{}", ctx.render.framed("✨", &format!("{} perks right up!", nybbler.styled_name())));
+                } else {
+                    println!("{}", style(format!("{} feels a little better knowing you're here.", nybbler.display_name())).dim().italic());
+                }
+                thread::sleep(Duration::from_millis(1000));
+            },
+            Action::Notes => {
+                nybbler.notes = prompt_notes(&nybbler)?;
+                println!("{}", ctx.render.framed("📝", &format!("Notes saved for {}!", nybbler.styled_name())));
+            },
+            Action::Help => {
+                println!("{}", style(ctx.render.labeled("❓", "How to care for your Nybbler")).bold().cyan());
+                println!("{}", help_text(&nybbler, ctx.render, &ctx.config));
+                println!();
+                println!("{}", style("Press any key to go back...").dim());
+                term.read_key()?;
+            },
+            Action::SwitchPet => {
+                nybbler.save()?;
+                match pick_other_pet(&nybbler, &term)? {
+                    Some((mut other, other_lock)) => {
+                        other.update(&ctx.config);
+
+                        if ctx.config.contagion {
+                            for (infected, source) in spread_sickness(&mut [&mut nybbler, &mut other], &mut ctx.rng) {
+                                println!("{}", ctx.render.labeled("🤧", &format!("{} caught something from {}!", infected, source)));
+                            }
+                        }
+
+                        // Pets seen together in a session keep each other company
+                        if ctx.config.companionship {
+                            let now = Local::now();
+                            for pet in [&mut nybbler, &mut other] {
+                                if pet.enjoy_company_at(now) {
+                                    println!("{}", ctx.render.labeled("😊", &format!("{} enjoyed the company!", pet.styled_name())));
+                                }
+                            }
+                        }
+
+                        if nybbler.left_behind() {
+                            println!("{}", ctx.render.labeled("🥺", &format!("{} looks a little lonely as you go.", nybbler.styled_name())));
+                        }
+
+                        nybbler.save()?;
+                        println!("{}", ctx.render.labeled("🔄", &format!("Now looking after {}!", other.styled_name())));
+                        nybbler = other;
+                        _lock = other_lock;
+                    },
+                    None => println!("{}", ctx.render.labeled("🐾", "There are no other pets to switch to yet.")),
+                }
+                thread::sleep(Duration::from_millis(1500));
+            },
+            Action::Exit => {
+                if confirm_exit(ctx.render)? {
+                    // Save the nybbler before exiting
+                    match nybbler.save() {
+                        Ok(_) => {
+                            println!("{}", ctx.render.framed("💾", &format!("{} has been saved successfully!", nybbler.styled_name())));
+                            thread::sleep(Duration::from_millis(1000));
+                        },
+                        Err(e) => {
+                            println!("Error saving nybbler: {}", e);
+                            thread::sleep(Duration::from_millis(1000));
+                        }
+                    }
+                    term.clear_screen()?;
+                    let goodbye = ctx.render.pick("
+      /\\_/\\
+     ( ^ω^ )
+     / >👋< \\
+    Goodbye!", "
+      /\\_/\\
+     ( ^w^ )
+     / >o< \\
+    Goodbye!");
+                    println!("{}", style(goodbye).bold().yellow());
+                    println!("{}", style(ctx.render.framed("👋", "Goodbye! See you soon!")).bold().green());
+                    println!("{}", ctx.render.framed("🌈", &format!("{} will be waiting for your return!", nybbler.display_name())));
+                    break;
+                }
+            },
+        }
+
+        // Spell out exactly how the action moved the numbers
+        if action.past_tense().is_some() {
+            if let Some(changes) = describe_stat_changes(before, nybbler.shown_stats(ctx.render), ctx.render) {
+                println!("{}", style(changes).cyan());
+            }
+            if nybbler.level > level_before {
+                println!("{}", style(level_up_message(&nybbler, ctx.render)).bold().green());
+            }
+            if nybbler.bond_tier() > bond_before {
+                println!("{}", style(bond_message(&nybbler, ctx.render)).bold().magenta());
+            }
+        }
+
+        // Short delay to see the action result
+        thread::sleep(Duration::from_millis(1000));
+    }
+
+    Ok(())
+}
+
+// What happened to one step of a care routine
+enum RoutineStep {
+    // The action was done, moving the shown stats from the first values to the second
+    Ran(Action, [u8; 5], [u8; 5]),
+    // The action was skipped for the given reason
+    Skipped(Action, String),
+}
+
+impl RoutineStep {
+    // Summarize the step on one line
+    fn describe(&self, render: Render, config: &Config) -> String {
+        match self {
+            RoutineStep::Ran(action, before, after) => {
+                let changes = describe_stat_changes(*before, *after, render).unwrap_or_else(|| "no change".to_string());
+                format!("  {} {}: {}", render.pick("✅", "+"), action.display_label(render, config), changes)
+            },
+            RoutineStep::Skipped(action, reason) => {
+                format!("  {} {}: skipped ({})", render.pick("⏭️", "-"), action.display_label(render, config), reason)
+            },
+        }
+    }
+}
+
+// Do each action of a routine in order, skipping any whose preconditions aren't met
+fn run_routine<R: Rng>(nybbler: &mut Nybbler, routine: &[Action], rng: &mut R, render: Render) -> Vec<RoutineStep> {
+    routine
+        .iter()
+        .map(|&action| {
+            if action.past_tense().is_none() {
+                return RoutineStep::Skipped(action, "not a care action".to_string());
+            }
+            if let Err(error) = nybbler.try_action(action) {
+                return RoutineStep::Skipped(action, error.short());
+            }
+            let before = nybbler.shown_stats(render);
+            nybbler.perform(action, rng);
+            RoutineStep::Ran(action, before, nybbler.shown_stats(render))
+        })
+        .collect()
+}
+
+// Describe which shown stats changed, like "Fullness +30 → 80, Energy +5 → 90"
+fn describe_stat_changes(before: [u8; 5], after: [u8; 5], render: Render) -> Option<String> {
+    let changes: Vec<String> = Stat::ALL
+        .iter()
+        .zip(before.iter().zip(after))
+        .filter(|(_, (&before, after))| before != *after)
+        .map(|(stat, (&before, after))| {
+            let delta = i16::from(after) - i16::from(before);
+            if render.accessible {
+                let direction = if delta > 0 { "up" } else { "down" };
+                format!("{} went {} by {} to {}", stat.label(render), direction, delta.abs(), after)
+            } else {
+                format!("{} {:+} {} {}", stat.label(render), delta, render.pick("→", "->"), after)
+            }
+        })
+        .collect();
+    if changes.is_empty() {
+        return None;
+    }
+
+    if render.accessible {
+        Some(format!("{}.", join_clauses(&changes)))
+    } else {
+        Some(changes.join(", "))
+    }
+}
+
+// Recap what happened since `since`, like "While you were away (2 days): Fullness -40 -> 3. Fluffy is now sick."
+fn away_recap(nybbler: &Nybbler, before: [u8; 5], mood_before: NybblerMood, since: DateTime<Local>, render: Render) -> Option<String> {
+    let away = nybbler.last_updated.signed_duration_since(since);
+    if away.num_seconds() < MIN_UPDATE_INTERVAL_SECS {
+        return None;
+    }
+
+    let mut recap = format!("While you were away ({})", format_away(away));
+    match describe_stat_changes(before, nybbler.shown_stats(render), render) {
+        Some(changes) if render.accessible => recap.push_str(&format!(", {}", changes)),
+        Some(changes) => recap.push_str(&format!(": {}.", changes)),
+        None => recap.push_str(", nothing much changed."),
+    }
+    if nybbler.mood != mood_before {
+        recap.push_str(&format!(" {} is now {}.", nybbler.display_name(), nybbler.mood.describe()));
+    }
+    Some(recap)
+}
+
+// Say roughly how long the player was away, in the largest whole unit
+fn format_away(away: chrono::Duration) -> String {
+    let (count, unit) = match (away.num_days(), away.num_hours(), away.num_minutes()) {
+        (0, 0, minutes) => (minutes, "minute"),
+        (0, hours, _) => (hours, "hour"),
+        (days, _, _) => (days, "day"),
+    };
+    if count == 1 {
+        format!("{} {}", count, unit)
+    } else {
+        format!("{} {}s", count, unit)
+    }
+}
+
+// Format a remaining cooldown compactly, like "1h 5m"
+fn format_remaining(remaining: chrono::Duration) -> String {
+    let minutes = (remaining.num_seconds() + 59) / 60;
+    match (minutes / 60, minutes % 60) {
+        (0, minutes) => format!("{}m", minutes),
+        (hours, 0) => format!("{}h", hours),
+        (hours, minutes) => format!("{}h {}m", hours, minutes),
+    }
+}
+
+// Give neglected pets a chance to catch whatever their sick housemates have,
+// returning who caught it from whom
+fn spread_sickness<R: Rng>(pets: &mut [&mut Nybbler], rng: &mut R) -> Vec<(String, String)> {
+    // Only pets that were already sick are contagious, so illness doesn't chain in one pass
+    let sick: Vec<String> = pets
+        .iter()
+        .filter(|pet| pet.is_alive() && pet.mood == NybblerMood::Sick)
+        .map(|pet| pet.name.clone())
+        .collect();
+
+    let mut infections = Vec::new();
+    for pet in pets.iter_mut() {
+        // Well cared for pets shrug it off
+        if !pet.is_alive() || pet.mood == NybblerMood::Sick || pet.wellness() >= HEALTHY_STAT_THRESHOLD {
+            continue;
+        }
+        let source = sick.iter().find(|&source| *source != pet.name && rng.gen_bool(CONTAGION_CHANCE));
+        if let Some(source) = source {
+            pet.health = pet.health.saturating_sub(CONTAGION_HEALTH);
+            pet.update_mood();
+            infections.push((pet.name.clone(), source.clone()));
+        }
+    }
+    infections
+}
+
+// Update and save every pet at once, letting sickness spread between them if enabled
+fn update_all(ctx: &mut GameContext) -> io::Result<()> {
+    let paths = list_save_files()?;
+    if paths.is_empty() {
+        println!("{}", NO_PETS_HINT);
+        return Ok(());
+    }
+
+    // Pets open in another session are skipped, and the rest stay locked until saved
+    let mut pets = Vec::new();
+    for (path, loaded) in paths.iter().zip(load_and_update(&paths, &ctx.config)) {
+        match loaded {
+            Ok((nybbler, lock)) => pets.push((path, nybbler, lock)),
+            Err(e) => eprintln!("Skipping {}: {}", path.display(), e),
+        }
+    }
+
+    if ctx.config.contagion {
+        let mut household: Vec<&mut Nybbler> = pets.iter_mut().map(|(_, pet, _)| pet).collect();
+        for (infected, source) in spread_sickness(&mut household, &mut ctx.rng) {
+            println!("{}", ctx.render.labeled("🤧", &format!("{} caught something from {}!", infected, source)));
+        }
+    }
+
+    // Each pet goes back to the file it came from, so no two threads share a file
+    for ((_, pet, _), saved) in pets.iter().zip(parallel_map(&pets, |(path, pet, _)| pet.save_path(path))) {
+        saved?;
+        println!("- {} - {:?}, wellness {}/100", pet.name, pet.mood, pet.wellness());
+    }
+    Ok(())
+}
+
+// Lock, load, and update every save file, spreading the work across threads
+// Results come back in the same order as the paths
+fn load_and_update(paths: &[PathBuf], config: &Config) -> Vec<Result<(Nybbler, PetLock), SaveError>> {
+    parallel_map(paths, |path| {
+        Nybbler::load_locked(path).map(|(mut nybbler, lock)| {
+            nybbler.update(config);
+            (nybbler, lock)
+        })
+    })
+}
+
+// Apply `f` to every item on a few scoped threads, keeping the results in order
+fn parallel_map<T: Sync, U: Send>(items: &[T], f: impl Fn(&T) -> U + Sync) -> Vec<U> {
+    let threads = thread::available_parallelism().map_or(1, |n| n.get());
+    let chunk_size = items.len().div_ceil(threads).max(1);
+    let f = &f;
+    thread::scope(|scope| {
+        let handles: Vec<_> = items
+            .chunks(chunk_size)
+            .map(|chunk| scope.spawn(move || chunk.iter().map(f).collect::<Vec<U>>()))
+            .collect();
+        handles.into_iter().flat_map(|handle| handle.join().expect("update thread panicked")).collect()
+    })
+}
+
+// Offer a happy ending to a well-loved Elder, archiving it if the player agrees
+// Returns whether the Nybbler graduated
+fn graduate(nybbler: &Nybbler, term: &Term, render: Render) -> io::Result<bool> {
+    let ready = format!(
+        "{} has had a long, happy life and is ready to graduate and move on. Their save will be archived.",
+        nybbler.name
+    );
+    println!("{}", style(render.labeled("🎓", &ready)).bold().green());
+    let proceed = dialoguer::Confirm::new()
+        .with_prompt(format!("Let {} graduate?", nybbler.name))
+        .default(false)
+        .interact()?;
+    if !proceed {
+        println!("{}", style(format!("{} is happy to stay a while longer.", nybbler.name)).dim());
+        thread::sleep(Duration::from_millis(1500));
+        return Ok(false);
+    }
+
+    nybbler.save()?;
+    let save_dir = get_save_directory()?;
+    move_save(&save_dir, &save_dir.join(ARCHIVE_DIR), &nybbler.name)?;
+
+    term.clear_screen()?;
+    let title = format!("Congratulations, {}!", nybbler.name);
+    println!("{}", style(render.labeled("🎓", &title)).bold().magenta());
+    for line in life_summary(nybbler) {
+        println!("{}", line);
+    }
+    println!();
+    println!("{}", render.labeled("🌈", "Thank you for giving them such a wonderful life!"));
+    println!("{}", style(format!("Run `nybbler unarchive {}` to visit them again.", nybbler.name)).dim());
+    Ok(true)
+}
+
+// A few lines looking back on a Nybbler's life
+fn life_summary(nybbler: &Nybbler) -> Vec<String> {
+    let mut lines = vec![format!(
+        "{} the {:?} lived {} days and grew into {}.",
+        nybbler.name,
+        nybbler.character_type,
+        nybbler.age,
+        nybbler.life_stage().with_article()
+    )];
+    if !nybbler.traits().is_empty() {
+        let traits: Vec<String> =
+            nybbler.traits().iter().map(|personality| format!("{:?}", personality).to_lowercase()).collect();
+        lines.push(format!("They were {}.", join_clauses(&traits)));
+    }
+    for (stage, at) in &nybbler.milestones {
+        lines.push(format!("Became {} on {}.", stage.with_article(), at.format("%Y-%m-%d")));
+    }
+    lines.push(format!(
+        "You looked after them every day for the last {} days.",
+        nybbler.care_streak(Local::now().date_naive())
+    ));
+    lines
+}
+
+// Let the player choose another living saved pet to look after
+fn pick_other_pet(current: &Nybbler, term: &Term) -> io::Result<Option<(Nybbler, PetLock)>> {
+    let current_file = save_file_name(&current.name);
+    let mut others = Vec::new();
+    for path in list_save_files()? {
+        if path.file_name().is_some_and(|name| *name == *current_file) {
+            continue;
+        }
+        if let Ok(pet) = Nybbler::load_path(&path) {
+            if pet.is_alive() {
+                others.push((path, pet));
+            }
+        }
+    }
+    if others.is_empty() {
+        return Ok(None);
+    }
+
+    let names: Vec<&str> = others.iter().map(|(_, pet)| pet.name.as_str()).collect();
+    let choice = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt("Which pet would you like to look after?")
+        .items(&names)
+        .default(0)
+        .interact_on(term)?;
+
+    // Read the pet again once it's locked, in case it changed while the menu was open
+    let (path, _) = others.swap_remove(choice);
+    Ok(Some(Nybbler::load_locked(&path)?))
+}
+
+// A short guide to the stats, actions, and care, built from the game's own numbers
+fn help_text(nybbler: &Nybbler, render: Render, config: &Config) -> String {
+    let mut lines = vec!["Stats".to_string()];
+    for stat in Stat::ALL {
+        lines.push(format!("  {}: {}", stat.label(render), stat.meaning(render)));
+    }
+
+    lines.push(String::new());
+    lines.push("Actions".to_string());
+    for &action in &config.menu {
+        lines.push(format!("  {}: {}", action.display_label(render, config), action.guide(render)));
+    }
+
+    lines.push(String::new());
+    lines.push("Tips".to_string());
+    lines.push(format!(
+        "  Keep every stat above {} and health slowly comes back on its own.",
+        HEALTHY_STAT_THRESHOLD
+    ));
+    lines.push(format!("  Stats below {} are flagged as critical.", nybbler.alert_threshold));
+    lines.push(format!(
+        "  {} loves {}: it gives +{} happiness, and skipping it for {} hours makes them sulk.",
+        nybbler.name,
+        nybbler.favorite_activity().describe(),
+        FAVORITE_ACTIVITY_BONUS,
+        FAVORITE_NEGLECT_HOURS
+    ));
+    lines.join("\n")
+}
+
+// Ask the player for new notes about their Nybbler, starting from the current ones
+fn prompt_notes(nybbler: &Nybbler) -> Result<String, std::io::Error> {
+    let notes = dialoguer::Input::<String>::new()
+        .with_prompt(format!("Notes about {}", nybbler.name))
+        .with_initial_text(nybbler.notes.clone())
+        .allow_empty(true)
+        .interact_text()?;
+
+    Ok(notes.trim().to_string())
+}
+
+// Where the menu should start: on whatever the Nybbler needs most, as long as
+// it can be done right now, or else on the first action
+fn suggested_action(nybbler: &Nybbler, actions: &[Action], now: DateTime<Local>) -> usize {
+    nybbler
+        .primary_need()
+        .filter(|&need| nybbler.try_action_at(need, now).is_ok())
+        .and_then(|need| actions.iter().position(|&action| action == need))
+        .unwrap_or(0)
+}
+
+// Debug tool: set each stat and the age directly to reproduce a specific state
+fn debug_set_stats(nybbler: &mut Nybbler, render: Render) -> io::Result<()> {
+    println!("{}", style("DEBUG: setting stats directly. Press Enter to keep a value.").bold().red());
+    // Values are entered as they're shown, so hunger may need turning back into fullness
+    for stat in Stat::ALL {
+        let shown = prompt_debug_value(stat.label(render), nybbler.shown_stat(stat, render).into(), 100)? as u8;
+        *nybbler.stat_mut(stat) = if stat == Stat::Hunger { render.hunger.store(shown) } else { shown };
+    }
+    nybbler.age = prompt_debug_value("Age in days", nybbler.age, u16::MAX)?;
+    nybbler.update_mood_at(Local::now());
+    eventlog::record(&format!(
+        "{}: stats set by hand (hunger {}, happiness {}, energy {}, health {}, age {})",
+        nybbler.name, nybbler.hunger, nybbler.happiness, nybbler.energy, nybbler.health, nybbler.age
+    ));
+    Ok(())
+}
+
+// Ask for one debug value, starting from the current one
+fn prompt_debug_value(label: &str, current: u16, max: u16) -> io::Result<u16> {
+    let text = dialoguer::Input::<String>::new()
+        .with_prompt(format!("{} (0-{})", label, max))
+        .with_initial_text(current.to_string())
+        .validate_with(|input: &String| parse_debug_value(input, current, max).map(|_| ()))
+        .interact_text()?;
+    Ok(parse_debug_value(&text, current, max).unwrap_or(current))
+}
+
+// Read a debug value, keeping the current one when blank and clamping anything too big
+fn parse_debug_value(text: &str, current: u16, max: u16) -> Result<u16, &'static str> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Ok(current);
+    }
+    match text.parse::<u64>() {
+        Ok(value) => Ok(value.min(u64::from(max)) as u16),
+        Err(_) => Err("Please enter a whole number"),
+    }
+}
+
+fn confirm_exit(render: Render) -> Result<bool, std::io::Error> {
+    let confirm = dialoguer::Confirm::new()
+        .with_prompt(render.framed("🥺", "Are you really sure you want to leave? Your Nybbler will miss you!"))
+        .default(false)
+        .interact()?;
+
+    Ok(confirm)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    // Create a Nybbler with a fixed character type for deterministic tests
+    fn test_nybbler() -> Nybbler {
+        Nybbler::new("Test".to_string(), StartingProfile::Standard, &mut StdRng::seed_from_u64(0))
+    }
+
+    // A test Nybbler without personality traits, so stats change at the base rates
+    fn plain_nybbler() -> Nybbler {
+        let mut nybbler = test_nybbler();
+        nybbler.personality.clear();
+        nybbler
+    }
+
+    // Config that lets every absence decay right away
+    fn no_grace() -> Config {
+        Config { grace_hours: 0.0, ..Config::default() }
+    }
+
+    // Plain ASCII output with the default horizontal bars
+    fn ascii() -> Render {
+        Render::new(true, false, false, BarLayout::Horizontal)
+    }
+
+    #[test]
+    fn well_cared_for_nybbler_recovers_health_over_time() {
+        let mut nybbler = test_nybbler();
+        nybbler.hunger = 100;
+        nybbler.happiness = 100;
+        nybbler.energy = 100;
+        nybbler.health = 50;
+
+        let start = nybbler.last_updated;
+        for hour in 1..=5 {
+            nybbler.update_at(start + chrono::Duration::hours(hour), &Config::default());
+        }
+
+        assert_eq!(nybbler.health, 50 + 5 * HEALTH_RECOVERY_PER_HOUR as u8);
+    }
+
+    #[test]
+    fn neglected_nybbler_does_not_recover_health() {
+        let mut nybbler = test_nybbler();
+        nybbler.hunger = 40;
+        nybbler.health = 50;
+
+        let start = nybbler.last_updated;
+        nybbler.update_at(start + chrono::Duration::hours(3), &Config::default());
+
+        assert_eq!(nybbler.health, 50);
+    }
+
+    #[test]
+    fn updates_within_the_minimum_interval_change_nothing() {
+        let mut nybbler = test_nybbler();
+        nybbler.hunger = 19;
+        nybbler.health = 80;
+
+        let start = nybbler.last_updated;
+        for seconds in [1, 10, 30, MIN_UPDATE_INTERVAL_SECS - 1] {
+            nybbler.update_at(start + chrono::Duration::seconds(seconds), &Config::default());
+        }
+
+        assert_eq!(nybbler.hunger, 19);
+        assert_eq!(nybbler.health, 80);
+        assert_eq!(nybbler.last_updated, start);
+
+        // Once the interval has passed, the accrued time is applied
+        nybbler.update_at(start + chrono::Duration::seconds(MIN_UPDATE_INTERVAL_SECS), &Config::default());
+        assert_eq!(nybbler.health, 75);
+    }
+
+    #[test]
+    fn loading_clamps_out_of_range_stats() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("overflow.json");
+        fs::write(
+            &path,
+            r#"{"name":"Overflow","hunger":250,"happiness":101,"energy":100,"health":255,"age":3,
+                "last_updated":"2024-01-01T00:00:00+00:00","mood":"Happy","character_type":"Cat"}"#,
+        )
+        .unwrap();
+
+        let nybbler = Nybbler::load_path(&path).unwrap();
+
+        assert_eq!(nybbler.hunger, 100);
+        assert_eq!(nybbler.happiness, 100);
+        assert_eq!(nybbler.energy, 100);
+        assert_eq!(nybbler.health, 100);
+    }
+
+    #[test]
+    fn save_file_names_ignore_case_and_surrounding_whitespace() {
+        assert_eq!(save_file_name("Fluffy"), "fluffy.json");
+        assert_eq!(save_file_name("  Fluffy \t"), "fluffy.json");
+        assert_eq!(save_file_name(" Mr Whiskers "), "mr whiskers.json");
+    }
+
+    #[test]
+    fn blank_and_unsafe_names_are_rejected() {
+        assert!(validate_name("").is_err());
+        assert!(validate_name("   \t ").is_err());
+        assert!(validate_name("../escape").is_err());
+        assert!(validate_name("  Fluffy  ").is_ok());
+    }
+
+    #[test]
+    fn well_fed_nybbler_sleeps_to_full_energy() {
+        let mut nybbler = test_nybbler();
+        nybbler.hunger = 100;
+        nybbler.energy = 10;
+
+        nybbler.sleep();
+
+        assert_eq!(nybbler.energy, 100);
+    }
+
+    #[test]
+    fn hungry_nybbler_recovers_less_energy_from_sleep() {
+        let mut starving = test_nybbler();
+        starving.hunger = 0;
+        starving.energy = 0;
+        starving.sleep();
+        assert_eq!(starving.energy, 30);
+
+        let mut peckish = test_nybbler();
+        peckish.hunger = 50;
+        peckish.energy = 0;
+        peckish.sleep();
+        assert_eq!(peckish.energy, 65);
+    }
+
+    #[test]
+    fn age_saturates_instead_of_wrapping() {
+        let mut nybbler = test_nybbler();
+        nybbler.age = u16::MAX - 1;
+
+        let start = nybbler.last_updated;
+        nybbler.update_at(start + chrono::Duration::days(10), &Config::default());
+
+        assert_eq!(nybbler.age, u16::MAX);
+    }
+
+    #[test]
+    fn diary_groups_events_by_day_and_skips_empty_days() {
+        let mut nybbler = test_nybbler();
+        let day = |d: u32, h: u32| Local.with_ymd_and_hms(2026, 3, d, h, 0, 0).unwrap();
+        nybbler.mood_history = VecDeque::from([
+            MoodEntry { at: day(1, 9), mood: NybblerMood::Excited },
+            MoodEntry { at: day(4, 9), mood: NybblerMood::Sick },
+            MoodEntry { at: day(4, 18), mood: NybblerMood::Happy },
+        ]);
+        nybbler.events = VecDeque::from([
+            ActionEvent { at: day(1, 10), action: Action::Play },
+            ActionEvent { at: day(1, 11), action: Action::Play },
+            ActionEvent { at: day(4, 12), action: Action::Heal },
+        ]);
+
+        assert_eq!(diary_entries(&nybbler), vec![
+            "Day 1 (2026-03-01): Test was excited and played twice.".to_string(),
+            "Day 4 (2026-03-04): Test was happy, took medicine once and caught a cold but recovered.".to_string(),
+        ]);
+    }
+
+    #[test]
+    fn feeding_out_of_range_stats_caps_without_overflow() {
+        let mut nybbler = test_nybbler();
+        nybbler.hunger = 250;
+        nybbler.energy = 255;
+
+        nybbler.feed();
+
+        assert_eq!(nybbler.hunger, 100);
+        assert_eq!(nybbler.energy, 100);
+    }
+
+    #[test]
+    fn feeding_near_the_cap_stops_at_100() {
+        let mut nybbler = test_nybbler();
+        nybbler.hunger = 90;
+        nybbler.energy = 98;
+
+        nybbler.feed();
+
+        assert_eq!(nybbler.hunger, 100);
+        assert_eq!(nybbler.energy, 100);
+    }
+
+    #[test]
+    fn toys_change_stats_by_their_own_effects() {
+        for toy in Toy::ALL {
+            let mut nybbler = test_nybbler();
+            // Keep the favorite-activity bonus out of the comparison
+            nybbler.happiness = 100;
+            let effect = toy.effect();
+
+            nybbler.play(toy);
+
+            assert_eq!(nybbler.hunger, 50u8.saturating_add_signed(effect.hunger));
+            assert_eq!(nybbler.energy, 100u8.saturating_add_signed(effect.energy).min(100));
+        }
+    }
+
+    #[test]
+    fn comfort_is_only_offered_to_sad_or_sick_nybblers() {
+        let mut nybbler = test_nybbler();
+        assert!(!nybbler.available_actions(&Config::default()).contains(&Action::Comfort));
+
+        nybbler.mood = NybblerMood::Sad;
+        assert!(nybbler.available_actions(&Config::default()).contains(&Action::Comfort));
+
+        nybbler.mood = NybblerMood::Sick;
+        assert!(nybbler.available_actions(&Config::default()).contains(&Action::Comfort));
+    }
+
+    #[test]
+    fn saves_of_identical_state_are_byte_identical() {
+        let at = Local.with_ymd_and_hms(2026, 3, 1, 9, 0, 0).unwrap();
+        let mut first = test_nybbler();
+        let mut second = test_nybbler();
+        for nybbler in [&mut first, &mut second] {
+            nybbler.last_updated = at;
+            nybbler.last_favorite_at = Some(at);
+        }
+
+        // Insert the same cooldowns in opposite orders
+        first.cooldowns.insert(Action::Sleep, at);
+        first.cooldowns.insert(Action::Heal, at);
+        second.cooldowns.insert(Action::Heal, at);
+        second.cooldowns.insert(Action::Sleep, at);
+
+        assert_eq!(serde_json::to_string_pretty(&first).unwrap(), serde_json::to_string_pretty(&second).unwrap());
+    }
+
+    #[test]
+    fn growing_up_is_announced_exactly_once() {
+        let mut nybbler = test_nybbler();
+        nybbler.hunger = 100;
+        nybbler.happiness = 100;
+        let start = nybbler.last_updated;
+
+        nybbler.update_at(start + chrono::Duration::days(3), &Config::default());
+        assert_eq!(nybbler.life_stage(), LifeStage::Child);
+        assert!(nybbler.milestones.contains_key(&LifeStage::Child));
+
+        assert_eq!(nybbler.take_milestone_announcements(), vec![LifeStage::Child]);
+        assert!(nybbler.take_milestone_announcements().is_empty());
+    }
+
+    #[test]
+    fn file_in_place_of_the_save_directory_is_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        let save_dir = dir.path().join("nybbler");
+        fs::write(&save_dir, "not a folder").unwrap();
+
+        let error = check_save_directory(&save_dir).unwrap_err();
+
+        assert!(error.to_string().contains("is a file"));
+        assert!(error.to_string().contains(&save_dir.display().to_string()));
+    }
+
+    #[test]
+    fn missing_or_existing_save_directory_is_fine() {
+        let dir = tempfile::tempdir().unwrap();
+        let save_dir = dir.path().join("nybbler");
+        assert!(check_save_directory(&save_dir).is_ok());
+
+        fs::create_dir(&save_dir).unwrap();
+        assert!(check_save_directory(&save_dir).is_ok());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn looping_symlink_save_directory_is_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        let save_dir = dir.path().join("nybbler");
+        std::os::unix::fs::symlink(&save_dir, &save_dir).unwrap();
+
+        let error = check_save_directory(&save_dir).unwrap_err();
+
+        assert!(error.to_string().contains("symlink"));
+    }
+
+    #[test]
+    fn archived_saves_move_back_and_forth_without_overwriting() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = dir.path().join(ARCHIVE_DIR);
+        fs::write(dir.path().join("fluffy.json"), "{}").unwrap();
+
+        move_save(dir.path(), &archive, "Fluffy").unwrap();
+        assert!(!dir.path().join("fluffy.json").exists());
+        assert!(archive.join("fluffy.json").exists());
+
+        // A new pet with the same name blocks the restore instead of being replaced
+        fs::write(dir.path().join("fluffy.json"), "{}").unwrap();
+        let error = move_save(&archive, dir.path(), "Fluffy").unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::AlreadyExists);
+
+        fs::remove_file(dir.path().join("fluffy.json")).unwrap();
+        move_save(&archive, dir.path(), "Fluffy").unwrap();
+        assert!(dir.path().join("fluffy.json").exists());
+    }
+
+    #[test]
+    fn timestamps_saved_in_another_time_zone_keep_their_instant() {
+        let instant = chrono::Utc.with_ymd_and_hms(2026, 3, 1, 0, 0, 0).unwrap();
+        let local = Local.offset_from_utc_datetime(&instant.naive_utc());
+
+        // Written by players in Tokyo and St. John's, but never in the zone the tests run in
+        let elsewhere = [chrono::FixedOffset::east_opt(9 * 3600), chrono::FixedOffset::west_opt(3 * 3600 + 1800)]
+            .map(Option::unwrap)
+            .into_iter()
+            .filter(|&offset| offset != local);
+        for offset in elsewhere {
+            let json = format!(r#"{{"at": "{}", "mood": "Happy"}}"#, instant.with_timezone(&offset).to_rfc3339());
+            let entry: MoodEntry = serde_json::from_str(&json).unwrap();
+            assert_eq!(entry.at, instant);
+            assert_eq!(*entry.at.offset(), local);
+
+            // Saving again writes the same instant in the local offset
+            let saved = serde_json::to_string(&entry).unwrap();
+            assert!(!saved.contains(&offset.to_string()));
+            let reloaded: MoodEntry = serde_json::from_str(&saved).unwrap();
+            assert_eq!(reloaded.at, instant);
+        }
+    }
+
+    #[test]
+    fn actions_explain_why_they_are_refused() {
+        let mut nybbler = test_nybbler();
+        let now = nybbler.last_updated;
+        assert_eq!(nybbler.try_action_at(Action::Play, now), Ok(()));
+
+        nybbler.energy = PLAY_MIN_ENERGY - 1;
+        assert_eq!(nybbler.try_action_at(Action::Play, now), Err(ActionError::TooTired));
+
+        nybbler.cooldowns.insert(Action::Sleep, now);
+        let later = now + chrono::Duration::minutes(10);
+        assert_eq!(
+            nybbler.try_action_at(Action::Sleep, later),
+            Err(ActionError::CoolingDown(chrono::Duration::minutes(SLEEP_COOLDOWN_MINUTES - 10)))
+        );
+
+        assert_eq!(nybbler.try_action_at(Action::Comfort, now), Err(ActionError::NotNeeded));
+    }
+
+    #[test]
+    fn company_cheers_up_at_most_once_per_cooldown() {
+        let mut nybbler = test_nybbler();
+        let now = nybbler.last_updated;
+
+        assert!(nybbler.enjoy_company_at(now));
+        assert_eq!(nybbler.happiness, 50 + COMPANY_HAPPINESS);
+
+        // Switching back and forth right away doesn't help again
+        assert!(!nybbler.enjoy_company_at(now + chrono::Duration::minutes(5)));
+        assert_eq!(nybbler.happiness, 50 + COMPANY_HAPPINESS);
+
+        assert!(nybbler.enjoy_company_at(now + chrono::Duration::hours(COMPANY_COOLDOWN_HOURS)));
+    }
+
+    #[test]
+    fn fast_forward_matches_a_real_absence() {
+        let now = Local::now();
+        let mut skipped = test_nybbler();
+        skipped.bond = 50;
+        skipped.last_updated = now;
+        skipped.fast_forward_at(2, now, &Config::default());
+
+        let mut away = test_nybbler();
+        away.bond = 50;
+        away.last_updated = now - chrono::Duration::days(2);
+        away.update_with_grace(now, &Config::default(), 0.0);
+
+        assert_eq!(skipped.age, 2);
+        assert!(skipped.bond < 50);
+        assert_eq!(
+            (skipped.hunger, skipped.happiness, skipped.energy, skipped.health, skipped.bond, skipped.age),
+            (away.hunger, away.happiness, away.energy, away.health, away.bond, away.age)
+        );
+    }
+
+    #[test]
+    fn adults_become_hardy_and_decay_slower() {
+        let mut adult = test_nybbler();
+        adult.age = 16;
+        let mut teen = test_nybbler();
+        teen.age = 15;
+        teen.last_updated = adult.last_updated;
+
+        let later = adult.last_updated + chrono::Duration::hours(1);
+        adult.update_at(later, &no_grace());
+        teen.update_at(later, &no_grace());
+        assert!(adult.hardy);
+        assert!(!teen.hardy);
+
+        let much_later = later + chrono::Duration::hours(1);
+        adult.update_at(much_later, &no_grace());
+        teen.update_at(much_later, &no_grace());
+        assert!(adult.hunger > teen.hunger);
+    }
+
+    #[test]
+    fn menu_follows_the_configured_order() {
+        let nybbler = test_nybbler();
+        let config = Config { menu: vec![Action::Play, Action::Feed, Action::Exit], ..Config::default() };
+
+        assert_eq!(nybbler.available_actions(&config), vec![Action::Play, Action::Feed, Action::Exit]);
+    }
+
+    #[test]
+    fn inspecting_a_save_reports_problems_without_writing() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("shared.json");
+        let mut nybbler = test_nybbler();
+        nybbler.hunger = 150;
+        fs::write(&path, serde_json::to_string(&nybbler).unwrap()).unwrap();
+
+        let (inspected, warnings) = inspect_save(&path).unwrap();
+        assert_eq!(inspected.hunger, 100);
+        assert_eq!(warnings.len(), 1);
+        assert!(fs::read_to_string(&path).unwrap().contains("150"));
+
+        nybbler.name = "bad/name".to_string();
+        fs::write(&path, serde_json::to_string(&nybbler).unwrap()).unwrap();
+        assert!(inspect_save(&path).is_err());
+
+        fs::write(&path, "not json").unwrap();
+        assert!(inspect_save(&path).is_err());
+    }
+
+    #[test]
+    fn sickness_only_spreads_to_neglected_pets_and_is_reproducible() {
+        let household = || {
+            let mut sick = test_nybbler();
+            sick.name = "Sick".to_string();
+            sick.mood = NybblerMood::Sick;
+            let mut neglected = test_nybbler();
+            neglected.name = "Neglected".to_string();
+            (neglected.hunger, neglected.happiness, neglected.energy, neglected.health) = (10, 10, 10, 80);
+            let mut healthy = test_nybbler();
+            healthy.name = "Healthy".to_string();
+            (healthy.hunger, healthy.happiness, healthy.energy, healthy.health) = (100, 100, 100, 100);
+            [sick, neglected, healthy]
+        };
+
+        let mut infected_once = false;
+        for seed in 0..50 {
+            let [mut a1, mut b1, mut c1] = household();
+            let [mut a2, mut b2, mut c2] = household();
+            let first = spread_sickness(&mut [&mut a1, &mut b1, &mut c1], &mut StdRng::seed_from_u64(seed));
+            let second = spread_sickness(&mut [&mut a2, &mut b2, &mut c2], &mut StdRng::seed_from_u64(seed));
+
+            assert_eq!(first, second);
+            assert!(first.iter().all(|(infected, source)| infected == "Neglected" && source == "Sick"));
+            infected_once |= !first.is_empty();
+        }
+        assert!(infected_once);
+    }
+
+    #[test]
+    fn help_is_built_from_the_real_action_effects() {
+        let render = ascii();
+        let config = Config::default();
+        let help = help_text(&test_nybbler(), render, &config);
+        for action in Action::ALL {
+            assert!(help.contains(&action.label(render)));
+        }
+        assert!(help.contains(&FEED_EFFECT.describe(render)));
+        assert!(help.contains(&format!("at least {} energy", PLAY_MIN_ENERGY)));
+        assert!(help.contains(&format!("wait {}m", SLEEP_COOLDOWN_MINUTES)));
+    }
+
+    #[test]
+    fn bulk_update_handles_many_pets_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let two_hours_ago = Local::now() - chrono::Duration::hours(2);
+        let paths: Vec<PathBuf> = (0..64)
+            .map(|i| {
+                let mut nybbler = test_nybbler();
+                nybbler.name = format!("Pet{}", i);
+                nybbler.last_updated = two_hours_ago;
+                let path = dir.path().join(format!("pet{}.json", i));
+                nybbler.save_path(&path).unwrap();
+                path
+            })
+            .collect();
+        fs::write(dir.path().join("broken.json"), "not a pet").unwrap();
+        let mut with_broken = paths.clone();
+        with_broken.insert(10, dir.path().join("broken.json"));
+
+        // A pet open in another session is skipped rather than saved over
+        let mut open_elsewhere = test_nybbler();
+        open_elsewhere.name = "Busy".to_string();
+        let busy_path = dir.path().join("busy.json");
+        open_elsewhere.save_path(&busy_path).unwrap();
+        let _session = PetLock::acquire(&busy_path).unwrap();
+        with_broken.insert(20, busy_path);
+
+        let results = load_and_update(&with_broken, &no_grace());
+        assert_eq!(results.len(), 66);
+        assert!(matches!(results[10], Err(SaveError::Parse(_))));
+        assert!(matches!(results[20], Err(SaveError::Locked)));
+        let pets: Vec<(&PathBuf, Nybbler)> = with_broken
+            .iter()
+            .zip(results)
+            .filter_map(|(path, loaded)| loaded.ok().map(|(pet, _)| (path, pet)))
+            .collect();
+        assert_eq!(pets.len(), 64);
+        for (i, (_, pet)) in pets.iter().enumerate() {
+            assert_eq!(pet.name, format!("Pet{}", i));
+            assert!(pet.hunger < test_nybbler().hunger);
+        }
+
+        let saved = parallel_map(&pets, |(path, pet)| pet.save_path(path));
+        assert!(saved.iter().all(Result::is_ok));
+        for (path, pet) in &pets {
+            let reloaded = Nybbler::load_path(path).unwrap();
+            assert_eq!(reloaded.hunger, pet.hunger);
+            assert!(reloaded.last_updated > two_hours_ago);
+        }
+    }
+
+    #[test]
+    fn actions_snapshot_stats_without_piling_up() {
+        let mut nybbler = test_nybbler();
+        nybbler.feed();
+        nybbler.play(Toy::Ball);
+        assert_eq!(nybbler.snapshots.len(), 1);
+        assert_eq!(nybbler.snapshots[0].hunger, nybbler.hunger);
+
+        let start = Local::now();
+        for i in 0..(SNAPSHOT_HISTORY_LEN as i64 + 50) {
+            nybbler.record_snapshot(start + chrono::Duration::minutes(10 * i));
+        }
+        assert_eq!(nybbler.snapshots.len(), SNAPSHOT_HISTORY_LEN);
+    }
+
+    #[test]
+    fn anonymizing_drops_notes_but_keeps_the_pet() {
+        let mut nybbler = test_nybbler();
+        nybbler.notes = "Lives with Sam on Elm Street".to_string();
+        nybbler.age = 12;
+        nybbler.hunger = 42;
+        nybbler.anonymize();
+        assert!(nybbler.notes.is_empty());
+        assert_eq!((nybbler.name.as_str(), nybbler.age, nybbler.hunger), ("Test", 12, 42));
+    }
+
+    #[test]
+    fn starting_profiles_set_the_initial_stats() {
+        let standard = test_nybbler();
+        assert_eq!([standard.hunger, standard.happiness, standard.energy, standard.health], [50, 50, 100, 100]);
+
+        let mut rng = StdRng::seed_from_u64(0);
+        let scrappy = Nybbler::new("Scrap".to_string(), StartingProfile::Scrappy, &mut rng);
+        let pampered = Nybbler::new("Posh".to_string(), StartingProfile::Pampered, &mut rng);
+        assert!(scrappy.wellness() < standard.wellness());
+        assert!(pampered.wellness() > standard.wellness());
+    }
+
+    #[test]
+    fn stat_changes_list_only_what_moved() {
+        let render = ascii();
+        let mut nybbler = test_nybbler();
+        let before = nybbler.shown_stats(render);
+        nybbler.feed();
+        let changes = describe_stat_changes(before, nybbler.shown_stats(render), render);
+        assert!(changes.unwrap().starts_with("Fullness +30 -> 80"));
+
+        let accessible = Render::new(false, false, true, BarLayout::Horizontal);
+        let changes = describe_stat_changes([50, 50, 80, 100, 100], [80, 50, 70, 100, 100], accessible);
+        assert_eq!(changes.as_deref(), Some("Fullness went up by 30 to 80 and Energy went down by 10 to 70."));
+        assert_eq!(describe_stat_changes([1, 2, 3, 4, 5], [1, 2, 3, 4, 5], render), None);
+    }
+
+    #[test]
+    fn wellness_trend_compares_with_the_previous_snapshot() {
+        let mut nybbler = test_nybbler();
+        let start = Local::now() - chrono::Duration::hours(2);
+        nybbler.record_snapshot(start);
+        assert_eq!(nybbler.wellness_trend(), None);
+
+        nybbler.hunger -= 20;
+        nybbler.record_snapshot(start + chrono::Duration::hours(1));
+        assert_eq!(nybbler.wellness_trend(), Some(Trend::Falling));
+
+        nybbler.feed();
+        assert_eq!(nybbler.wellness_trend(), Some(Trend::Rising));
+    }
+
+    #[test]
+    fn personality_traits_change_decay_and_gains() {
+        let mut plain = test_nybbler();
+        plain.personality = Vec::new();
+        plain.last_updated = Local::now() - chrono::Duration::hours(1);
+        let mut glutton = test_nybbler();
+        glutton.personality = vec![Personality::Gluttonous, Personality::Shy];
+        glutton.last_updated = plain.last_updated;
+
+        let now = Local::now();
+        plain.update_at(now, &no_grace());
+        glutton.update_at(now, &no_grace());
+        assert!(glutton.hunger < plain.hunger);
+        assert_eq!(glutton.energy, plain.energy);
+
+        let happiness = glutton.happiness;
+        glutton.feed();
+        assert!(glutton.happiness >= happiness + GLUTTON_FEED_HAPPINESS);
+
+        let happiness = glutton.happiness;
+        assert!(glutton.left_behind());
+        assert_eq!(glutton.happiness, happiness - SHY_LEFT_BEHIND_HAPPINESS);
+        assert!(!plain.left_behind());
+    }
+
+    #[test]
+    fn mixed_case_saves_are_renamed_to_lowercase() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("Fluffy.json"), "fluffy").unwrap();
+        fs::write(dir.path().join("Biscuit.json"), "old biscuit").unwrap();
+        fs::write(dir.path().join("biscuit.json"), "new biscuit").unwrap();
+        fs::write(dir.path().join("Notes.txt"), "not a save").unwrap();
+
+        let renamed = normalize_save_file_names(dir.path()).unwrap();
+        assert_eq!(renamed, vec!["Fluffy.json".to_string()]);
+        assert_eq!(fs::read_to_string(dir.path().join("fluffy.json")).unwrap(), "fluffy");
+        // A clash is left alone for dedupe, and nothing else is touched
+        assert!(dir.path().join("Biscuit.json").exists());
+        assert_eq!(fs::read_to_string(dir.path().join("biscuit.json")).unwrap(), "new biscuit");
+        assert!(dir.path().join("Notes.txt").exists());
+        assert!(normalize_save_file_names(dir.path()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn numeric_stats_list_one_stat_per_line() {
+        let render = ascii();
+        let mut nybbler = test_nybbler();
+        nybbler.energy = 5;
+        let table = numeric_stats(&nybbler, render);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[0], "Fullness     50/100");
+        assert!(lines[2].starts_with("Energy") && lines[2].ends_with("!!"));
+        assert_eq!(lines[4], "Cleanliness 100/100");
+    }
+
+    #[test]
+    fn well_loved_elders_can_graduate() {
+        let now = Local::now();
+        let mut nybbler = test_nybbler();
+        nybbler.age = 60;
+        (nybbler.hunger, nybbler.happiness, nybbler.energy, nybbler.health) = (90, 90, 90, 100);
+        for day in (0..GRADUATION_STREAK_DAYS).rev() {
+            let at = now - chrono::Duration::days(i64::from(day));
+            nybbler.events.push_back(ActionEvent { at, action: Action::Feed });
+            nybbler.record_snapshot(at);
+        }
+        assert_eq!(nybbler.care_streak(now.date_naive()), GRADUATION_STREAK_DAYS);
+        assert!(nybbler.can_graduate(now));
+
+        // A single bad day in the window spoils it
+        nybbler.snapshots[2].hunger = 0;
+        nybbler.snapshots[2].happiness = 0;
+        assert!(!nybbler.can_graduate(now));
+
+        // So does being too young
+        let mut adult = test_nybbler();
+        adult.age = 59;
+        adult.events = nybbler.events.clone();
+        assert!(!adult.can_graduate(now));
+    }
+
+    #[test]
+    fn reads_wait_out_a_save_that_is_being_written() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.json");
+        let json = serde_json::to_string_pretty(&test_nybbler()).unwrap();
+        fs::write(&path, &json[..json.len() / 2]).unwrap();
+
+        // Another session finishes writing while the reader is still waiting to retry
+        let writer = {
+            let path = path.clone();
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(READ_RETRY_MILLIS / 4));
+                fs::write(path, json).unwrap();
+            })
+        };
+        assert_eq!(Nybbler::read_path(&path).unwrap().name, "Test");
+        writer.join().unwrap();
+
+        fs::write(&path, "still broken").unwrap();
+        assert!(Nybbler::read_path(&path).is_err());
+    }
+
+    #[test]
+    fn only_one_pet_is_favorite_at_a_time() {
+        let dir = tempfile::tempdir().unwrap();
+        let paths: Vec<PathBuf> = ["Fluffy", "Biscuit"]
+            .iter()
+            .map(|name| {
+                let mut nybbler = test_nybbler();
+                nybbler.name = name.to_string();
+                let path = dir.path().join(save_file_name(name));
+                nybbler.save_path(&path).unwrap();
+                path
+            })
+            .collect();
+        let favorites = || -> Vec<bool> { paths.iter().map(|p| Nybbler::load_path(p).unwrap().is_favorite).collect() };
+
+        assert!(set_favorite(&paths, "fluffy").unwrap());
+        assert_eq!(favorites(), vec![true, false]);
+        assert!(set_favorite(&paths, "Biscuit").unwrap());
+        assert_eq!(favorites(), vec![false, true]);
+        assert!(!set_favorite(&paths, "Nobody").unwrap());
+        assert_eq!(favorites(), vec![false, true]);
+    }
+
+    #[test]
+    fn short_absences_fall_within_the_grace_period() {
+        let config = Config { grace_hours: 3.0, ..Config::default() };
+        let mut nybbler = test_nybbler();
+        let start = nybbler.last_updated;
+        nybbler.update_at(start + chrono::Duration::hours(2), &config);
+        let fresh = test_nybbler();
+        assert_eq!(
+            (nybbler.hunger, nybbler.happiness, nybbler.energy, nybbler.health),
+            (fresh.hunger, fresh.happiness, fresh.energy, fresh.health)
+        );
+
+        let mut longer = test_nybbler();
+        longer.update_at(start + chrono::Duration::hours(4), &config);
+        assert!(longer.hunger < fresh.hunger);
+    }
+
+    #[test]
+    fn only_a_dying_pet_pulses_in_fancy_output() {
+        let fancy = Render::new(false, true, false, BarLayout::Horizontal);
+        let mut nybbler = test_nybbler();
+        assert!(!nybbler.shows_heartbeat(fancy));
+        nybbler.health = HEARTBEAT_HEALTH - 1;
+        assert!(nybbler.shows_heartbeat(fancy));
+        assert!(!nybbler.shows_heartbeat(ascii()));
+        assert!(!nybbler.shows_heartbeat(Render::new(false, true, true, BarLayout::Horizontal)));
+
+        let art = nybbler.life_stage().frame_art(nybbler.character_type.neutral());
+        let pulse = pulse_frame(&art);
+        assert_eq!(pulse.lines().count(), art.lines().count());
+        assert_eq!(pulse.replace(' ', ""), art.replace(' ', ""));
+        assert_ne!(pulse, art);
+    }
+
+    #[test]
+    fn daily_mood_is_fixed_per_day_and_nudges_happiness() {
+        let start = test_nybbler().last_updated;
+        let later = start + chrono::Duration::minutes(30);
+        let date = later.date_naive();
+        assert_eq!(DailyMood::for_day("Test", date), DailyMood::for_day(" test ", date));
+        let moods: Vec<DailyMood> = (0..100).map(DailyMood::from_seed).collect();
+        assert!(moods.contains(&DailyMood::Grumpy) && moods.contains(&DailyMood::Cheerful));
+
+        // Find a grumpy and a cheerful name for the same day and compare them
+        let named = |mood: DailyMood| (0..).map(|i| format!("Pet{}", i)).find(|name| DailyMood::for_day(name, date) == mood).unwrap();
+        let mut grumpy = test_nybbler();
+        grumpy.name = named(DailyMood::Grumpy);
+        let mut cheerful = test_nybbler();
+        cheerful.name = named(DailyMood::Cheerful);
+        grumpy.update_with_grace(later, &no_grace(), 0.0);
+        cheerful.update_with_grace(later, &no_grace(), 0.0);
+        assert!(grumpy.happiness < cheerful.happiness);
+        assert!(cheerful.happiness - grumpy.happiness <= 2);
+    }
+
+    #[test]
+    fn load_errors_say_what_went_wrong() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.json");
+        assert!(matches!(Nybbler::load_path(&path), Err(SaveError::NotFound(_))));
+        assert!(matches!(Nybbler::load("../escape"), Err(SaveError::InvalidName(_))));
+
+        fs::write(&path, "{ not a pet").unwrap();
+        assert!(matches!(Nybbler::load_path(&path), Err(SaveError::Parse(_))));
+        let backup = back_up_damaged_save(&path).unwrap();
+        assert!(!path.exists());
+        assert_eq!(fs::read_to_string(backup).unwrap(), "{ not a pet");
+    }
+
+    #[test]
+    fn debug_values_are_clamped_or_kept() {
+        assert_eq!(parse_debug_value(" 5 ", 80, 100), Ok(5));
+        assert_eq!(parse_debug_value("250", 80, 100), Ok(100));
+        assert_eq!(parse_debug_value("99999999999", 3, u16::MAX), Ok(u16::MAX));
+        assert_eq!(parse_debug_value("", 80, 100), Ok(80));
+        assert!(parse_debug_value("-4", 80, 100).is_err());
+        assert!(parse_debug_value("lots", 80, 100).is_err());
+    }
+
+    #[test]
+    fn returning_players_get_a_recap_of_their_absence() {
+        let render = ascii();
+        let mut nybbler = test_nybbler();
+        let (before, mood, since) = (nybbler.shown_stats(render), nybbler.mood, nybbler.last_updated);
+        assert_eq!(away_recap(&nybbler, before, mood, since, render), None);
+
+        nybbler.hunger = before[0] - 40;
+        nybbler.health = 20;
+        nybbler.mood = NybblerMood::Sick;
+        nybbler.last_updated = since + chrono::Duration::days(2);
+        let recap = away_recap(&nybbler, before, mood, since, render).unwrap();
+        assert!(recap.starts_with("While you were away (2 days): Fullness -40"));
+        assert!(recap.ends_with("Test is now sick."));
+        assert_eq!(format_away(chrono::Duration::minutes(61)), "1 hour");
+    }
+
+    #[test]
+    fn the_least_recently_played_pets_make_room_first() {
+        let start = Local::now();
+        let pets = || -> Vec<Nybbler> {
+            ["Old", "Fav", "Newer", "Newest"]
+                .iter()
+                .enumerate()
+                .map(|(i, name)| {
+                    let mut pet = test_nybbler();
+                    pet.name = name.to_string();
+                    pet.last_updated = start + chrono::Duration::hours(i as i64);
+                    pet.is_favorite = *name == "Fav";
+                    pet
+                })
+                .collect()
+        };
+        assert_eq!(pets_to_prune(pets(), 10), Some(Vec::new()));
+        assert_eq!(pets_to_prune(pets(), 4), Some(vec!["Old".to_string()]));
+        assert_eq!(pets_to_prune(pets(), 3), Some(vec!["Old".to_string(), "Newer".to_string()]));
+        assert_eq!(pets_to_prune(pets(), 1), None);
+    }
+
+    #[test]
+    fn the_lowest_stat_decides_the_primary_need() {
+        let mut nybbler = test_nybbler();
+        (nybbler.hunger, nybbler.happiness, nybbler.energy, nybbler.health) = (100, 100, 100, 100);
+        assert_eq!(nybbler.primary_need(), None);
+        nybbler.energy = 10;
+        assert_eq!(nybbler.primary_need(), Some(Action::Sleep));
+        nybbler.hunger = 5;
+        assert_eq!(nybbler.primary_need(), Some(Action::Feed));
+        nybbler.health = 0;
+        assert_eq!(nybbler.primary_need(), Some(Action::Heal));
+    }
+
+    #[test]
+    fn backups_round_trip_and_check_each_entry() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut paths = Vec::new();
+        for name in ["Mochi", "Tofu"] {
+            let mut nybbler = test_nybbler();
+            nybbler.name = name.to_string();
+            let path = dir.path().join(save_file_name(name));
+            nybbler.save_path(&path).unwrap();
+            paths.push(path);
+        }
+        fs::write(dir.path().join("broken.json"), "not a pet").unwrap();
+        paths.push(dir.path().join("broken.json"));
+
+        let out = dir.path().join("backup.out");
+        assert_eq!(write_backup(&paths, &out).unwrap(), (2, 1));
+        let names: Vec<String> = read_backup(&out).unwrap().into_iter().map(|pet| pet.unwrap().name).collect();
+        assert_eq!(names, ["Mochi", "Tofu"]);
+
+        fs::write(&out, r#"[{"name": "Half a pet"}]"#).unwrap();
+        assert!(read_backup(&out).unwrap()[0].is_err());
+        fs::write(&out, "{}").unwrap();
+        assert!(read_backup(&out).is_err());
+    }
+
+    #[test]
+    fn long_names_are_shortened_only_on_screen() {
+        let mut nybbler = test_nybbler();
+        nybbler.name = "🐙".repeat(10) + &"Long".repeat(20);
+        assert_eq!(nybbler.name.chars().count(), 90);
+        nybbler.name.push_str(&"界".repeat(10));
+        let shown = nybbler.display_name();
+        assert!(shown.ends_with('…'));
+        assert!(console::measure_text_width(&shown) <= NAME_DISPLAY_WIDTH);
+        assert_eq!(console::measure_text_width(&nybbler.styled_name().to_string()), console::measure_text_width(&shown));
+        assert!(save_file_name(&nybbler.name).starts_with(&nybbler.name.to_lowercase()));
+
+        nybbler.name = "Mochi".to_string();
+        assert_eq!(nybbler.display_name(), "Mochi");
+    }
+
+    #[test]
+    fn actions_earn_xp_and_levels_make_care_more_effective() {
+        let mut nybbler = test_nybbler();
+        nybbler.hunger = 10;
+        nybbler.feed();
+        assert_eq!((nybbler.level, nybbler.xp), (1, XP_PER_ACTION));
+        assert_eq!(nybbler.hunger, 40);
+
+        // Enough experience rolls over into the next level
+        nybbler.gain_xp(XP_PER_LEVEL);
+        assert_eq!((nybbler.level, nybbler.xp), (2, XP_PER_ACTION));
+
+        nybbler.level = 6;
+        nybbler.hunger = 10;
+        nybbler.feed();
+        assert_eq!(nybbler.hunger, 43);
+
+        // The bonus stops growing past a point
+        nybbler.level = 50;
+        assert_eq!(nybbler.level_bonus_percent(), MAX_LEVEL_BONUS_PERCENT);
+    }
+
+    #[test]
+    fn ascii_mood_glyphs_are_distinct_and_used_without_emoji() {
+        let moods = [
+            NybblerMood::Happy,
+            NybblerMood::Neutral,
+            NybblerMood::Sad,
+            NybblerMood::Sick,
+            NybblerMood::Sleeping,
+            NybblerMood::Excited,
+            NybblerMood::Playful,
+            NybblerMood::Depressed,
+        ];
+        let glyphs: BTreeSet<&str> = moods.iter().map(|mood| mood.to_ascii()).collect();
+        assert_eq!(glyphs.len(), moods.len());
+        assert!(glyphs.iter().all(|glyph| glyph.is_ascii()));
+
+        assert_eq!(NybblerMood::Sick.glyph(ascii()), "x_x");
+        assert!(render_card(&test_nybbler(), ascii()).contains("Mood: :) Happy"));
+    }
+
+    #[test]
+    fn routine_runs_care_actions_in_order_and_skips_the_rest() {
+        let mut nybbler = test_nybbler();
+        nybbler.energy = 0;
+        nybbler.hunger = 50;
+        let render = ascii();
+        let mut rng = StdRng::seed_from_u64(1);
+
+        let steps = run_routine(&mut nybbler, &[Action::Feed, Action::Play, Action::Help, Action::Sleep], &mut rng, render);
+
+        assert!(matches!(steps[0], RoutineStep::Ran(Action::Feed, _, _)));
+        assert!(matches!(&steps[1], RoutineStep::Skipped(Action::Play, reason) if reason == "too tired"));
+        assert!(matches!(steps[2], RoutineStep::Skipped(Action::Help, _)));
+        assert!(matches!(steps[3], RoutineStep::Ran(Action::Sleep, _, _)));
+        assert_eq!(nybbler.hunger, 80);
+        assert!(nybbler.cooldown_remaining(Action::Sleep, Local::now()).is_some());
+    }
+
+    #[test]
+    fn bond_builds_with_steady_care_and_fades_slowly() {
+        let mut nybbler = test_nybbler();
+        nybbler.feed();
+        nybbler.feed();
+        // Only the first of a burst of actions counts
+        assert_eq!(nybbler.bond, BOND_CARE_GAIN);
+
+        nybbler.last_bonded_at = Some(Local::now() - chrono::Duration::hours(BOND_GAIN_HOURS));
+        nybbler.feed();
+        assert_eq!(nybbler.bond, 2 * BOND_CARE_GAIN);
+
+        nybbler.bond = 50;
+        assert_eq!(nybbler.bond_tier(), BondTier::Companion);
+        let start = nybbler.last_updated;
+        nybbler.update_at(start + chrono::Duration::days(3), &no_grace());
+        assert_eq!(nybbler.bond, 50 - 3 * BOND_LOSS_PER_DAY);
+        assert_eq!(nybbler.bond_tier(), BondTier::Friend);
+    }
+
+    #[test]
+    fn interrupted_save_leaves_the_previous_save_intact() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.json");
+        let mut nybbler = test_nybbler();
+        nybbler.save_path(&path).unwrap();
+
+        // A save killed partway through only ever got as far as the temporary file
+        let temp_path = path.with_extension("json.tmp");
+        nybbler.hunger = 12;
+        let json = serde_json::to_string_pretty(&nybbler).unwrap();
+        fs::write(&temp_path, &json[..json.len() / 2]).unwrap();
+        assert_eq!(Nybbler::load_path(&path).unwrap().hunger, test_nybbler().hunger);
+
+        // The next save replaces the leftover and cleans it up
+        nybbler.save_path(&path).unwrap();
+        assert_eq!(Nybbler::load_path(&path).unwrap().hunger, 12);
+        assert!(!temp_path.exists());
+    }
+
+    #[test]
+    fn dirty_nybblers_lose_health_until_bathed() {
+        let mut nybbler = test_nybbler();
+        let start = nybbler.last_updated;
+        nybbler.update_at(start + chrono::Duration::hours(1), &no_grace());
+        assert_eq!(nybbler.cleanliness, 98);
+
+        nybbler.cleanliness = 10;
+        let health = nybbler.health;
+        nybbler.update_at(start + chrono::Duration::hours(2), &no_grace());
+        assert_eq!(nybbler.health, health - NEGLECT_HEALTH_LOSS);
+
+        let happiness = nybbler.happiness;
+        nybbler.clean();
+        assert_eq!(nybbler.cleanliness, 100);
+        assert_eq!(nybbler.happiness, happiness + CLEAN_HAPPINESS);
+    }
+
+    #[test]
+    fn renaming_moves_the_save_without_clobbering_another_pet() {
+        let dir = tempfile::tempdir().unwrap();
+        test_nybbler().save_path(&dir.path().join("test.json")).unwrap();
+        let mut other = test_nybbler();
+        other.name = "Biscuit".to_string();
+        other.save_path(&dir.path().join("biscuit.json")).unwrap();
+
+        let renamed = rename_save(dir.path(), "Test", " Fluffy ").unwrap();
+        assert_eq!(renamed.name, "Fluffy");
+        assert!(!dir.path().join("test.json").exists());
+        assert_eq!(Nybbler::load_path(&dir.path().join("fluffy.json")).unwrap().name, "Fluffy");
+
+        // Only the capitalization changes, so the file stays put
+        assert_eq!(rename_save(dir.path(), "fluffy", "FLUFFY").unwrap().name, "FLUFFY");
+        assert!(dir.path().join("fluffy.json").exists());
+
+        assert!(matches!(rename_save(dir.path(), "Fluffy", "biscuit"), Err(SaveError::Io(_))));
+        assert_eq!(Nybbler::load_path(&dir.path().join("biscuit.json")).unwrap().name, "Biscuit");
+        assert!(matches!(rename_save(dir.path(), "Nobody", "Somebody"), Err(SaveError::NotFound(_))));
+    }
+
+    #[test]
+    fn mood_history_keeps_only_changes_up_to_its_cap() {
+        let mut nybbler = test_nybbler();
+        let start = nybbler.last_updated;
+        nybbler.set_mood(NybblerMood::Happy, start);
+        assert!(nybbler.mood_history.is_empty());
+
+        for i in 0..(MOOD_HISTORY_LEN as i64 + 20) {
+            let mood = if i % 2 == 0 { NybblerMood::Sad } else { NybblerMood::Happy };
+            nybbler.set_mood(mood, start + chrono::Duration::minutes(i));
+        }
+        assert_eq!(nybbler.mood_history.len(), MOOD_HISTORY_LEN);
+        assert_eq!(nybbler.mood_history.back().unwrap().at, start + chrono::Duration::minutes(MOOD_HISTORY_LEN as i64 + 19));
+    }
+
+    #[test]
+    fn save_directory_prefers_the_override_then_the_data_dir_then_the_working_dir() {
+        let working_dir = Path::new("/work");
+        let data_dir = Some(PathBuf::from("/data"));
+
+        let chosen = choose_save_directory(Some(OsString::from("/custom")), data_dir.clone(), working_dir);
+        assert_eq!(chosen, PathBuf::from("/custom"));
+
+        // An empty override counts as unset
+        let chosen = choose_save_directory(Some(OsString::new()), data_dir.clone(), working_dir);
+        assert_eq!(chosen, PathBuf::from("/data/nybbler"));
+
+        assert_eq!(choose_save_directory(None, data_dir, working_dir), PathBuf::from("/data/nybbler"));
+        assert_eq!(choose_save_directory(None, None, working_dir), PathBuf::from("/work/.nybbler"));
+    }
+
+    #[test]
+    fn decay_rates_come_from_the_config() {
+        let config = Config {
+            hunger_decay_per_hour: 1.0,
+            happiness_decay_per_hour: 0.0,
+            energy_decay_per_hour: 10.0,
+            ..no_grace()
+        };
+        let mut nybbler = plain_nybbler();
+        let (hunger, energy) = (nybbler.hunger, nybbler.energy);
+        let start = nybbler.last_updated;
+        nybbler.update_at(start + chrono::Duration::hours(1), &config);
+
+        assert_eq!(nybbler.hunger, hunger - 1);
+        assert_eq!(nybbler.energy, energy - 10);
+    }
+
+    #[test]
+    fn decay_scales_with_the_whole_gap() {
+        let mut nybbler = plain_nybbler();
+        nybbler.hunger = 100;
+        let start = nybbler.last_updated;
+        nybbler.update_at(start + chrono::Duration::hours(10), &no_grace());
+        assert_eq!(nybbler.hunger, 50);
+
+        let mut nybbler = plain_nybbler();
+        nybbler.hunger = 100;
+        nybbler.update_at(start + chrono::Duration::minutes(6), &no_grace());
+        assert!(nybbler.hunger >= 99);
+    }
+
+    #[test]
+    fn imports_take_the_given_name_and_reject_bad_json() {
+        let mut exported = test_nybbler();
+        exported.hunger = 150;
+        let json = serde_json::to_string_pretty(&exported).unwrap();
+
+        let imported = parse_import(&json, " Biscuit ").unwrap();
+        assert_eq!(imported.name, "Biscuit");
+        assert_eq!(imported.hunger, 100);
+
+        assert!(parse_import("{ not a pet", "Biscuit").is_err());
+        assert!(parse_import(&json, "../escape").is_err());
+    }
+
+    #[test]
+    fn unversioned_saves_migrate_to_the_current_version() {
+        let json = r#"{
+            "name": "Oldie",
+            "hunger": 80,
+            "happiness": 70,
+            "energy": 60,
+            "health": 90,
+            "age": 20,
+            "last_updated": "2024-01-01T12:00:00+00:00",
+            "mood": "Happy",
+            "character_type": "Cat",
+            "events": [
+                { "at": "2024-01-01T09:00:00+00:00", "action": "Feed" },
+                { "at": "2024-01-01T10:00:00+00:00", "action": "Play" },
+                { "at": "2024-01-02T09:00:00+00:00", "action": "Feed" }
+            ]
+        }"#;
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("oldie.json");
+        fs::write(&path, json).unwrap();
+
+        let nybbler = Nybbler::load_path(&path).unwrap();
+        assert_eq!(nybbler.version, SAVE_VERSION);
+        assert_eq!(nybbler.level, 1);
+        assert_eq!(nybbler.bond, 2);
+        assert_eq!(nybbler.cleanliness, 100);
+        assert_eq!(test_nybbler().version, SAVE_VERSION);
+    }
+
+    #[test]
+    fn snapshots_keep_their_resolution_through_an_active_session() {
+        let mut nybbler = test_nybbler();
+        let start = nybbler.last_updated;
+        for minute in 0..60 {
+            nybbler.hunger = 100 - minute as u8;
+            nybbler.record_snapshot(start + chrono::Duration::minutes(minute));
+        }
+
+        assert_eq!(nybbler.snapshots.len(), 12);
+        // Each one holds the latest state from its five minutes
+        let last = nybbler.snapshots.back().unwrap();
+        assert_eq!(last.at, start + chrono::Duration::minutes(55));
+        assert_eq!(last.hunger, 41);
+    }
+
+    #[test]
+    fn status_output_ignores_the_hunger_display() {
+        let mut nybbler = test_nybbler();
+        nybbler.hunger = 80;
+        assert!(render_status(&nybbler).lines().any(|line| line == "hunger: 80"));
+    }
+
+    #[test]
+    fn a_dirty_pet_needs_a_bath() {
+        let mut nybbler = test_nybbler();
+        (nybbler.hunger, nybbler.happiness, nybbler.energy, nybbler.health) = (90, 90, 90, 90);
+        nybbler.cleanliness = 10;
+        assert_eq!(nybbler.primary_need(), Some(Action::Clean));
+        assert_eq!(nybbler.critical_stats(), vec![Stat::Cleanliness]);
+    }
+
+    #[test]
+    fn minute_by_minute_updates_add_up_to_an_hour() {
+        let mut nybbler = plain_nybbler();
+        (nybbler.hunger, nybbler.happiness, nybbler.energy, nybbler.health) = (100, 100, 100, 50);
+        let start = nybbler.last_updated;
+        nybbler.recent_treats = 3;
+        nybbler.treats_forgotten_at = Some(start);
+        for minute in 1..=60 {
+            nybbler.update_at(start + chrono::Duration::minutes(minute), &Config::default());
+        }
+        assert_eq!(nybbler.hunger, 95);
+        assert_eq!(nybbler.health, 50 + HEALTH_RECOVERY_PER_HOUR as u8);
+        assert_eq!(nybbler.recent_treats, 2);
+    }
+
+    #[test]
+    fn saves_from_a_newer_version_are_refused() {
+        let mut nybbler = test_nybbler();
+        nybbler.version = SAVE_VERSION + 1;
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.json");
+        fs::write(&path, serde_json::to_string(&nybbler).unwrap()).unwrap();
+
+        assert!(matches!(Nybbler::load_path(&path), Err(SaveError::Parse(_))));
+        assert!(nybbler.migrate().is_err());
+        assert_eq!(nybbler.version, SAVE_VERSION + 1);
+
+        nybbler.version = SAVE_VERSION;
+        nybbler.migrate().unwrap();
+        assert_eq!(nybbler.version, SAVE_VERSION);
+    }
+
+    #[test]
+    fn daily_moods_hash_the_same_everywhere() {
+        assert_eq!(fnv1a(*b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a(*b"a"), 0xaf63_dc4c_8601_ec8c);
+        let date = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+        assert_eq!(DailyMood::for_day(" Fluffy ", date), DailyMood::for_day("fluffy", date));
+    }
+
+    #[test]
+    fn status_reports_the_updated_save() {
+        let config = no_grace();
+        let mut nybbler = plain_nybbler();
+        nybbler.hunger = 80;
+        nybbler.last_updated = Local::now() - chrono::Duration::hours(2);
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(save_file_name(&nybbler.name));
+        nybbler.save_path(&path).unwrap();
+
+        let status = refresh_status(dir.path(), &nybbler.name, &config).unwrap();
+        assert!(status.lines().any(|line| line == "hunger: 70"), "{}", status);
+        assert!(Nybbler::load_path(&path).unwrap().last_updated > nybbler.last_updated);
+        assert!(matches!(refresh_status(dir.path(), "Nobody", &config), Err(SaveError::NotFound(_))));
+    }
+
+    #[test]
+    fn grace_periods_spare_neglected_pets_every_loss() {
+        let config = Config { grace_hours: 3.0, ..Config::default() };
+        let away_for = |hours| {
+            let mut nybbler = plain_nybbler();
+            let start = nybbler.last_updated;
+            (nybbler.hunger, nybbler.happiness, nybbler.health) = (10, 50, 80);
+            nybbler.last_favorite_at = Some(start - chrono::Duration::hours(FAVORITE_NEGLECT_HOURS));
+            nybbler.update_at(start + chrono::Duration::hours(hours), &config);
+            nybbler
+        };
+
+        let sheltered = away_for(2);
+        assert_eq!((sheltered.hunger, sheltered.happiness, sheltered.health), (10, 50, 80));
+
+        let neglected = away_for(4);
+        assert!(neglected.health < 80);
+        assert!(neglected.happiness < 50 - FAVORITE_NEGLECT_PENALTY);
+    }
+
+    #[test]
+    fn vertical_bars_fill_one_column_per_stat() {
+        let mut nybbler = test_nybbler();
+        (nybbler.hunger, nybbler.happiness, nybbler.energy, nybbler.health, nybbler.cleanliness) = (100, 55, 0, 10, 100);
+        let bars = console::strip_ansi_codes(&vertical_stat_bars(&nybbler, ascii())).to_string();
+        let lines: Vec<&str> = bars.lines().collect();
+        assert_eq!(lines.len(), 12);
+        assert_eq!(lines[0].matches("###").count(), 2);
+        assert_eq!(lines[4].matches("###").count(), 3);
+        assert_eq!(lines[9].matches("###").count(), 4);
+        assert_eq!(lines[10].split_whitespace().collect::<Vec<_>>(), ["100", "55", "0", "10", "100"]);
+        assert!(lines[11].contains("Fullness") && lines[11].contains("Cleanliness"));
+    }
+
+    #[test]
+    fn cards_follow_the_display_flags() {
+        let mut nybbler = test_nybbler();
+        (nybbler.hunger, nybbler.happiness, nybbler.energy, nybbler.health, nybbler.cleanliness) = (100, 55, 0, 10, 100);
+        // The cat's art has no blocks to mistake for bars
+        nybbler.character_type = characters::CharacterType::Cat;
+
+        let mut numeric = ascii();
+        numeric.numeric = true;
+        let card = render_card(&nybbler, numeric);
+        assert!(card.contains("| Happiness    55/100"), "{}", card);
+        assert!(!card.contains('#'));
+
+        let vertical = Render::new(true, false, false, BarLayout::Vertical);
+        let card = render_card(&nybbler, vertical);
+        let rows: Vec<&str> = card.lines().filter(|line| line.contains("###")).collect();
+        assert_eq!(rows.len(), 10);
+        assert_eq!(rows[0].matches("###").count(), 2);
+        assert_eq!(rows[9].matches("###").count(), 4);
+        assert!(card.lines().any(|line| line.split_whitespace().eq(["|", "100", "55", "0", "10", "100", "|"])));
+        assert_eq!(card.lines().map(console::measure_text_width).collect::<BTreeSet<_>>().len(), 1);
+    }
+
+    #[test]
+    fn menus_start_on_the_primary_need_when_it_can_be_done() {
+        let mut nybbler = test_nybbler();
+        nybbler.energy = 10;
+        let actions = [Action::Feed, Action::Play, Action::Sleep, Action::Exit];
+        let now = Local::now();
+        assert_eq!(suggested_action(&nybbler, &actions, now), 2);
+
+        nybbler.cooldowns.insert(Action::Sleep, now);
+        assert_eq!(suggested_action(&nybbler, &actions, now), 0);
+    }
+
+    #[test]
+    fn card_rows_line_up() {
+        for render in [ascii(), Render::new(false, true, false, BarLayout::Horizontal)] {
+            let card = render_card(&test_nybbler(), render);
+            let widths: BTreeSet<usize> = card.lines().map(console::measure_text_width).collect();
+            assert_eq!(widths.len(), 1, "{}", card);
+        }
+    }
+
+    #[test]
+    fn a_locked_pet_is_refused_until_the_lock_is_dropped() {
+        let dir = tempfile::tempdir().unwrap();
+        test_nybbler().save_path(&dir.path().join("test.json")).unwrap();
+        let config = Config::default();
+
+        let (_, lock) = Nybbler::load_locked(&dir.path().join("test.json")).unwrap();
+        assert!(matches!(refresh_status(dir.path(), "Test", &config), Err(SaveError::Locked)));
+        drop(lock);
+        assert!(refresh_status(dir.path(), "Test", &config).is_ok());
+
+        // Looking for a pet that isn't there leaves no lock file behind
+        assert!(matches!(refresh_status(dir.path(), "Nobody", &config), Err(SaveError::NotFound(_))));
+        assert!(!dir.path().join("nobody.lock").exists());
+    }
+
+    #[test]
+    fn listing_can_tell_when_a_pet_is_open_elsewhere() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.json");
+        test_nybbler().save_path(&path).unwrap();
+
+        assert!(!PetLock::is_held(&path));
+        assert!(!dir.path().join("test.lock").exists());
+        let lock = PetLock::acquire(&path).unwrap();
+        assert!(PetLock::is_held(&path));
+        drop(lock);
+        assert!(!PetLock::is_held(&path));
+    }
+
+    #[test]
+    fn ascii_art_is_plain_and_keeps_its_shape() {
+        for &character in characters::CharacterType::value_variants() {
+            for art in [character.neutral(), character.eating(), character.sleeping(), character.playing(), character.healing()] {
+                let plain = ascii().art(art);
+                assert!(plain.is_ascii(), "{}", plain);
+                let widths = |art: &str| art.lines().map(console::measure_text_width).collect::<Vec<_>>();
+                assert_eq!(widths(&plain), widths(art));
+            }
+        }
+
+        let mut nybbler = test_nybbler();
+        nybbler.character_type = characters::CharacterType::Cat;
+        assert!(render_card(&nybbler, ascii()).is_ascii());
+    }
+
+    #[test]
+    fn saves_from_before_difficulty_was_deferred_still_load() {
+        let mut json: serde_json::Value = serde_json::to_value(test_nybbler()).unwrap();
+        json["difficulty"] = serde_json::json!("Hard");
+        let (nybbler, _) = Nybbler::from_json(&json.to_string()).unwrap();
+        assert_eq!(nybbler.name, "Test");
+    }
+
+    #[test]
+    fn comfort_counts_as_care_and_keeps_the_snapshot_in_step() {
+        let mut outcomes = BTreeSet::new();
+        for seed in 0..20 {
+            let mut nybbler = plain_nybbler();
+            nybbler.happiness = 5;
+            nybbler.update_mood();
+            let (xp, bond) = (nybbler.xp, nybbler.bond);
+
+            let lifted = nybbler.comfort(&mut StdRng::seed_from_u64(seed));
+            outcomes.insert(lifted);
+            assert_eq!(nybbler.xp, xp + XP_PER_ACTION);
+            assert_eq!(nybbler.bond, bond + BOND_COMFORT_GAIN);
+            assert_eq!(nybbler.mood, if lifted { NybblerMood::Neutral } else { NybblerMood::Sad });
+            assert_eq!(nybbler.snapshots.back().unwrap().mood, nybbler.mood);
+        }
+        assert_eq!(outcomes.len(), 2);
+    }
+}
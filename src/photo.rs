@@ -0,0 +1,244 @@
+// Pet photos saved as PNG images, only built with the `image` feature
+// Text is drawn with a tiny built-in bitmap font, so no font files are needed
+
+use std::fs::File;
+use std::io::{self, BufWriter};
+use std::path::Path;
+
+use crate::render::Render;
+use crate::{Nybbler, PetColor, Stat};
+
+// Size of one font pixel in the image
+const SCALE: usize = 3;
+
+// A text cell fits a 5x7 glyph with a pixel of spacing around it
+const CHAR_WIDTH: usize = 6 * SCALE;
+const CHAR_HEIGHT: usize = 9 * SCALE;
+
+// Art cells are twice as tall as they are wide, like terminal cells
+const ART_CELL_HEIGHT: usize = 2 * CHAR_WIDTH;
+
+// Layout of the photo, in image pixels
+const WIDTH: usize = 500;
+const MARGIN: usize = 24;
+const LABEL_WIDTH: usize = 10 * CHAR_WIDTH;
+const BAR_WIDTH: usize = 200;
+const ROW_GAP: usize = 4 * SCALE;
+
+type Rgba = [u8; 4];
+
+// A dark theme, so the pet's colors stand out
+const BACKGROUND: Rgba = [30, 30, 46, 255];
+const TEXT: Rgba = [205, 214, 244, 255];
+const BAR_TRACK: Rgba = [69, 71, 90, 255];
+const ART: Rgba = [249, 226, 175, 255];
+const ART_SHADE: Rgba = [223, 189, 120, 255];
+
+// An RGBA image being drawn
+struct Canvas {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgba>,
+}
+
+impl Canvas {
+    fn new(width: usize, height: usize) -> Self {
+        Canvas { width, height, pixels: vec![BACKGROUND; width * height] }
+    }
+
+    // Fill a rectangle, clipping anything that falls off the image
+    fn fill(&mut self, x: usize, y: usize, width: usize, height: usize, color: Rgba) {
+        for row in y..(y + height).min(self.height) {
+            for column in x..(x + width).min(self.width) {
+                self.pixels[row * self.width + column] = color;
+            }
+        }
+    }
+
+    // Draw one glyph with its top-left corner at (x, y)
+    fn glyph(&mut self, x: usize, y: usize, rows: [u8; 7], color: Rgba) {
+        for (row, bits) in rows.iter().enumerate() {
+            for column in 0..5 {
+                if bits & (0b10000 >> column) != 0 {
+                    self.fill(x + (column + 1) * SCALE, y + (row + 1) * SCALE, SCALE, SCALE, color);
+                }
+            }
+        }
+    }
+
+    // Draw a line of text; characters the font lacks are left blank
+    fn text(&mut self, x: usize, y: usize, text: &str, color: Rgba) {
+        for (i, c) in text.chars().enumerate() {
+            if let Some(rows) = glyph(c) {
+                self.glyph(x + i * CHAR_WIDTH, y, rows, color);
+            }
+        }
+    }
+
+    // Draw one cell of character art, keeping the shape of the block characters
+    fn art_cell(&mut self, x: usize, y: usize, c: char) {
+        let half = ART_CELL_HEIGHT / 2;
+        match c {
+            ' ' => {},
+            '█' => self.fill(x, y, CHAR_WIDTH, ART_CELL_HEIGHT, ART),
+            '▓' => self.fill(x, y, CHAR_WIDTH, ART_CELL_HEIGHT, ART_SHADE),
+            '▀' => self.fill(x, y, CHAR_WIDTH, half, ART),
+            '▄' => self.fill(x, y + half, CHAR_WIDTH, half, ART),
+            _ => match glyph(c) {
+                Some(rows) => self.glyph(x, y + (ART_CELL_HEIGHT - CHAR_HEIGHT) / 2, rows, ART),
+                // Eyes, hearts, and other symbols become a small dot
+                None => self.fill(x + 2 * SCALE, y + half - SCALE, 2 * SCALE, 2 * SCALE, ART),
+            },
+        }
+    }
+
+    // Encode the image as a PNG file
+    fn write_png(&self, path: &Path) -> io::Result<()> {
+        let file = BufWriter::new(File::create(path)?);
+        let mut encoder = png::Encoder::new(file, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().map_err(io::Error::other)?;
+        writer.write_image_data(self.pixels.as_flattened()).map_err(io::Error::other)?;
+        writer.finish().map_err(io::Error::other)
+    }
+}
+
+// Save a photo of the Nybbler's art and stat bars to `path`
+pub fn save_photo(nybbler: &Nybbler, render: Render, path: &Path) -> io::Result<()> {
+    draw(nybbler, render).write_png(path)
+}
+
+// Lay out the name, the art for the current mood, and a bar for each stat
+fn draw(nybbler: &Nybbler, render: Render) -> Canvas {
+    // The photo draws blocks as pixels, whatever the terminal can show
+    let art = nybbler.mood_art(Render { ascii: false, ..render });
+    let art: Vec<&str> = art.lines().skip_while(|line| line.trim().is_empty()).collect();
+    let art_columns = art.iter().map(|line| line.chars().count()).max().unwrap_or(0);
+    let row_height = CHAR_HEIGHT + ROW_GAP;
+    let height = MARGIN * 4 + CHAR_HEIGHT + art.len() * ART_CELL_HEIGHT + Stat::ALL.len() * row_height;
+    let mut canvas = Canvas::new(WIDTH, height);
+
+    let mut y = MARGIN;
+    canvas.text(MARGIN, y, &format!("{} THE NYBBLER", nybbler.name.to_uppercase()), pet_color(nybbler.color));
+    y += CHAR_HEIGHT + MARGIN;
+
+    let art_x = WIDTH.saturating_sub(art_columns * CHAR_WIDTH) / 2;
+    for line in &art {
+        for (i, c) in line.chars().enumerate() {
+            canvas.art_cell(art_x + i * CHAR_WIDTH, y, c);
+        }
+        y += ART_CELL_HEIGHT;
+    }
+    y += MARGIN;
+
+    for stat in Stat::ALL {
+        let value = nybbler.shown_stat(stat, render);
+        canvas.text(MARGIN, y, &stat.label(render).to_uppercase(), TEXT);
+        let bar_x = MARGIN + LABEL_WIDTH;
+        let bar_y = y + SCALE;
+        canvas.fill(bar_x, bar_y, BAR_WIDTH, CHAR_HEIGHT - 2 * SCALE, BAR_TRACK);
+        canvas.fill(bar_x, bar_y, BAR_WIDTH * usize::from(value.min(100)) / 100, CHAR_HEIGHT - 2 * SCALE, stat_color(stat));
+        canvas.text(bar_x + BAR_WIDTH + CHAR_WIDTH, y, &value.to_string(), TEXT);
+        y += row_height;
+    }
+    canvas
+}
+
+// The photo's version of each pet's signature color
+fn pet_color(color: PetColor) -> Rgba {
+    match color {
+        PetColor::Yellow => [249, 226, 175, 255],
+        PetColor::Cyan => [137, 220, 235, 255],
+        PetColor::Green => [166, 227, 161, 255],
+        PetColor::Magenta => [245, 194, 231, 255],
+        PetColor::Blue => [137, 180, 250, 255],
+    }
+}
+
+// Bar colors matching the ones on the stats screen
+fn stat_color(stat: Stat) -> Rgba {
+    match stat {
+        Stat::Hunger => [137, 180, 250, 255],
+        Stat::Happiness => [245, 194, 231, 255],
+        Stat::Energy => [250, 179, 135, 255],
+        Stat::Health => [243, 139, 168, 255],
+        Stat::Cleanliness => [148, 226, 213, 255],
+    }
+}
+
+// Rows of a 5x7 glyph, high bit on the left; lowercase letters share the capitals
+fn glyph(c: char) -> Option<[u8; 7]> {
+    let rows = match c.to_ascii_uppercase() {
+        'A' => [0b01110, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001],
+        'B' => [0b11110, 0b10001, 0b10001, 0b11110, 0b10001, 0b10001, 0b11110],
+        'C' => [0b01110, 0b10001, 0b10000, 0b10000, 0b10000, 0b10001, 0b01110],
+        'D' => [0b11110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b11110],
+        'E' => [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b11111],
+        'F' => [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b10000],
+        'G' => [0b01110, 0b10001, 0b10000, 0b10111, 0b10001, 0b10001, 0b01111],
+        'H' => [0b10001, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001],
+        'I' => [0b01110, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110],
+        'J' => [0b00111, 0b00010, 0b00010, 0b00010, 0b00010, 0b10010, 0b01100],
+        'K' => [0b10001, 0b10010, 0b10100, 0b11000, 0b10100, 0b10010, 0b10001],
+        'L' => [0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b11111],
+        'M' => [0b10001, 0b11011, 0b10101, 0b10101, 0b10001, 0b10001, 0b10001],
+        'N' => [0b10001, 0b10001, 0b11001, 0b10101, 0b10011, 0b10001, 0b10001],
+        'O' => [0b01110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110],
+        'P' => [0b11110, 0b10001, 0b10001, 0b11110, 0b10000, 0b10000, 0b10000],
+        'Q' => [0b01110, 0b10001, 0b10001, 0b10001, 0b10101, 0b10010, 0b01101],
+        'R' => [0b11110, 0b10001, 0b10001, 0b11110, 0b10100, 0b10010, 0b10001],
+        'S' => [0b01111, 0b10000, 0b10000, 0b01110, 0b00001, 0b00001, 0b11110],
+        'T' => [0b11111, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100],
+        'U' => [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110],
+        'V' => [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01010, 0b00100],
+        'W' => [0b10001, 0b10001, 0b10001, 0b10101, 0b10101, 0b10101, 0b01010],
+        'X' => [0b10001, 0b10001, 0b01010, 0b00100, 0b01010, 0b10001, 0b10001],
+        'Y' => [0b10001, 0b10001, 0b01010, 0b00100, 0b00100, 0b00100, 0b00100],
+        'Z' => [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b11111],
+        '0' => [0b01110, 0b10001, 0b10011, 0b10101, 0b11001, 0b10001, 0b01110],
+        '1' => [0b00100, 0b01100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110],
+        '2' => [0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b01000, 0b11111],
+        '3' => [0b11111, 0b00010, 0b00100, 0b00010, 0b00001, 0b10001, 0b01110],
+        '4' => [0b00010, 0b00110, 0b01010, 0b10010, 0b11111, 0b00010, 0b00010],
+        '5' => [0b11111, 0b10000, 0b11110, 0b00001, 0b00001, 0b10001, 0b01110],
+        '6' => [0b00110, 0b01000, 0b10000, 0b11110, 0b10001, 0b10001, 0b01110],
+        '7' => [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b01000, 0b01000],
+        '8' => [0b01110, 0b10001, 0b10001, 0b01110, 0b10001, 0b10001, 0b01110],
+        '9' => [0b01110, 0b10001, 0b10001, 0b01111, 0b00001, 0b00010, 0b01100],
+        '-' => [0b00000, 0b00000, 0b00000, 0b11111, 0b00000, 0b00000, 0b00000],
+        '.' => [0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b01100, 0b01100],
+        '!' => [0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00000, 0b00100],
+        '/' => [0b00001, 0b00010, 0b00010, 0b00100, 0b01000, 0b01000, 0b10000],
+        '\'' => [0b00100, 0b00100, 0b01000, 0b00000, 0b00000, 0b00000, 0b00000],
+        _ => return None,
+    };
+    Some(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::render::BarLayout;
+    use crate::StartingProfile;
+
+    #[test]
+    fn photos_show_the_art_and_every_stat() {
+        let nybbler = Nybbler::new("Test".to_string(), StartingProfile::Standard, &mut StdRng::seed_from_u64(0));
+        let render = Render::new(true, false, false, BarLayout::Horizontal);
+        let canvas = draw(&nybbler, render);
+        assert_eq!(canvas.pixels.len(), canvas.width * canvas.height);
+        assert!(canvas.pixels.contains(&ART));
+        for stat in Stat::ALL {
+            assert!(canvas.pixels.contains(&stat_color(stat)));
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.png");
+        canvas.write_png(&path).unwrap();
+        assert!(std::fs::read(&path).unwrap().starts_with(b"\x89PNG"));
+    }
+}
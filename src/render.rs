@@ -0,0 +1,106 @@
+// Rendering options for the current terminal
+// Decides between emoji-rich output and a plain ASCII fallback
+
+use std::borrow::Cow;
+use std::env;
+
+use clap::ValueEnum;
+use console::Term;
+
+use crate::characters;
+use crate::config::{AgeUnits, HungerDisplay};
+
+// How text should be drawn for the current session
+#[derive(Clone, Copy)]
+pub struct Render {
+    pub ascii: bool,
+    // Describe everything in plain sentences for screen readers
+    pub accessible: bool,
+    // Which way the stat bars are drawn
+    pub bars: BarLayout,
+    // Show the stats as plain numbers instead of bars
+    pub numeric: bool,
+    // Whether hunger reads as fullness or as hunger
+    pub hunger: HungerDisplay,
+    // Whether ages read in days or in weeks and months
+    pub age_units: AgeUnits,
+}
+
+// How the stat bars are laid out on the stats screen
+#[derive(Clone, Copy, PartialEq, Debug, Default, ValueEnum)]
+pub enum BarLayout {
+    // One bar per line, filling left to right
+    #[default]
+    Horizontal,
+    // Side-by-side columns, filling bottom to top, for narrow terminals
+    Vertical,
+}
+
+impl Render {
+    // Choose ASCII or Unicode output, letting explicit flags override detection
+    pub fn new(force_ascii: bool, force_unicode: bool, accessible: bool, bars: BarLayout) -> Self {
+        // Screen readers spell out emoji, so accessible output is always ASCII
+        let ascii = if force_ascii || accessible {
+            true
+        } else if force_unicode {
+            false
+        } else {
+            !terminal_supports_unicode()
+        };
+        Render {
+            ascii,
+            accessible,
+            bars,
+            numeric: false,
+            hunger: HungerDisplay::default(),
+            age_units: AgeUnits::default(),
+        }
+    }
+
+    // Pick the fancy or plain version of some text
+    pub fn pick<'a>(self, fancy: &'a str, plain: &'a str) -> &'a str {
+        if self.ascii {
+            plain
+        } else {
+            fancy
+        }
+    }
+
+    // Prefix text with an emoji, dropping the emoji in ASCII mode
+    pub fn labeled(self, emoji: &str, text: &str) -> String {
+        if self.ascii {
+            text.to_string()
+        } else {
+            format!("{} {}", emoji, text)
+        }
+    }
+
+    // Draw character art, redrawn with plain characters in ASCII mode
+    pub fn art(self, art: &str) -> Cow<'_, str> {
+        if self.ascii {
+            Cow::Owned(characters::plain_art(art))
+        } else {
+            Cow::Borrowed(art)
+        }
+    }
+
+    // Put an emoji on both sides of some text, dropping them in ASCII mode
+    pub fn framed(self, emoji: &str, text: &str) -> String {
+        if self.ascii {
+            text.to_string()
+        } else {
+            format!("{} {} {}", emoji, text, emoji)
+        }
+    }
+}
+
+// Guess whether the terminal can show emoji and box-drawing characters
+fn terminal_supports_unicode() -> bool {
+    // The Linux console and dumb terminals have no emoji glyphs
+    if matches!(env::var("TERM").as_deref(), Ok("dumb") | Ok("linux")) {
+        return false;
+    }
+
+    // Checks for an interactive terminal with a UTF-8 locale
+    Term::stdout().features().wants_emoji()
+}
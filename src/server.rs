@@ -0,0 +1,157 @@
+// Tiny HTTP endpoint for status pages, only built with the `server` feature
+// Every request locks, loads, updates, and saves the pet, then returns its save as JSON
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::time::Duration;
+
+use crate::config::Config;
+use crate::{get_save_directory, save_file_name, validate_name, Nybbler, SaveError};
+
+// Longest a client may stall while sending its request
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+// Most bytes of request line and headers read from one client
+const MAX_REQUEST_BYTES: u64 = 8192;
+
+// Answer requests like `GET /fluffy` until the process is stopped
+pub fn serve(host: &str, port: u16) -> io::Result<()> {
+    let listener = TcpListener::bind((host, port))?;
+    println!("Serving Nybblers on http://{}:{}/<name> (Ctrl+C to stop)", host, port);
+    for stream in listener.incoming() {
+        // One broken connection shouldn't take the server down
+        let result = stream.and_then(handle);
+        if let Err(e) = result {
+            eprintln!("Request failed: {}", e);
+        }
+    }
+    Ok(())
+}
+
+// Read one request and write back the response
+fn handle(mut stream: TcpStream) -> io::Result<()> {
+    // A slow or endless client shouldn't tie the server up
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut reader = BufReader::new((&stream).take(MAX_REQUEST_BYTES));
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+
+    // Read the rest of the headers so the client isn't cut off mid-request
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 0 && header.trim() != "" {
+        header.clear();
+    }
+
+    // A request that used up the whole budget was cut off, so it isn't acted on
+    let (status, body) = if reader.get_ref().limit() == 0 {
+        ("431 Request Header Fields Too Large", error_json("request too large"))
+    } else {
+        respond(&request_line)
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )
+}
+
+// Work out the status and JSON body for a request line like "GET /fluffy HTTP/1.1"
+fn respond(request_line: &str) -> (&'static str, String) {
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(path)) = (parts.next(), parts.next()) else {
+        return ("400 Bad Request", error_json("malformed request"));
+    };
+    if method != "GET" {
+        return ("405 Method Not Allowed", error_json("only GET is supported"));
+    }
+
+    let Some(name) = percent_decode(path.trim_start_matches('/')) else {
+        return ("400 Bad Request", error_json("malformed path"));
+    };
+
+    // Reload the config every time so edits apply without a restart
+    let config = Config::load();
+    let result = get_save_directory().map_err(SaveError::from).and_then(|dir| {
+        validate_name(&name).map_err(SaveError::InvalidName)?;
+        let path = dir.join(save_file_name(&name));
+        let (mut nybbler, _lock) = Nybbler::load_locked(&path)?;
+        nybbler.update(&config);
+        nybbler.save_path(&path)?;
+        Ok(serde_json::to_string_pretty(&nybbler).map_err(io::Error::other)?)
+    });
+    match result {
+        Ok(json) => ("200 OK", json),
+        Err(SaveError::NotFound(_) | SaveError::InvalidName(_)) => {
+            ("404 Not Found", error_json(&format!("no Nybbler named {}", name)))
+        },
+        Err(SaveError::Locked) => ("409 Conflict", error_json(&format!("{} is open in another nybbler session", name))),
+        Err(e) => ("500 Internal Server Error", error_json(&e.to_string())),
+    }
+}
+
+// Decode the %XX escapes in a request path, or None if one is malformed
+// or the decoded bytes aren't UTF-8
+fn percent_decode(path: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(path.len());
+    let mut rest = path.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        if byte == b'%' {
+            let hex = tail.get(..2).filter(|hex| hex.iter().all(u8::is_ascii_hexdigit))?;
+            bytes.push(u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok()?);
+            rest = &tail[2..];
+        } else {
+            bytes.push(byte);
+            rest = tail;
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+// Wrap an error message in a small JSON object
+fn error_json(message: &str) -> String {
+    serde_json::json!({ "error": message }).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_well_formed_gets_are_answered() {
+        assert_eq!(respond("").0, "400 Bad Request");
+        assert_eq!(respond("POST /fluffy HTTP/1.1").0, "405 Method Not Allowed");
+        assert_eq!(respond("GET /../etc HTTP/1.1").0, "404 Not Found");
+        assert_eq!(respond("GET /fluffy%2 HTTP/1.1").0, "400 Bad Request");
+    }
+
+    #[test]
+    fn paths_are_percent_decoded() {
+        assert_eq!(percent_decode("Mr%20Whiskers").as_deref(), Some("Mr Whiskers"));
+        assert_eq!(percent_decode("Z%C3%B6e%21").as_deref(), Some("Zöe!"));
+        assert_eq!(percent_decode("100%"), None);
+        assert_eq!(percent_decode("%zz"), None);
+        assert_eq!(percent_decode("%+1"), None);
+        assert_eq!(percent_decode("%FF"), None);
+    }
+
+    #[test]
+    fn oversized_requests_are_refused() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let client = std::thread::spawn(move || {
+            let mut stream = TcpStream::connect(address).unwrap();
+            // Send exactly the budget with the headers still unfinished, so
+            // nothing is left unread when the server hangs up
+            let mut request = "GET /fluffy HTTP/1.1\r\nX-Padding: ".to_string();
+            request.push_str(&"a".repeat(MAX_REQUEST_BYTES as usize - request.len()));
+            stream.write_all(request.as_bytes()).unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        });
+        handle(listener.accept().unwrap().0).unwrap();
+        assert!(client.join().unwrap().starts_with("HTTP/1.1 431"));
+    }
+}
@@ -0,0 +1,257 @@
+// Full-screen terminal view that draws the Nybbler in fixed panels
+// Everything redraws in place instead of scrolling like the classic view
+
+use std::io;
+use std::time::Duration;
+
+use chrono::Local;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Gauge, List, ListState, Paragraph, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+
+use crate::characters::Moment;
+use crate::config::Config;
+use crate::render::Render;
+use crate::{Action, GameContext, LifeStage, Nybbler, NybblerMood, Stat, Toy};
+
+// How often the view refreshes while waiting for a key press
+const REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+// Run the full-screen game loop until the player exits or the Nybbler dies
+pub fn run(nybbler: &mut Nybbler, ctx: &mut GameContext) -> io::Result<()> {
+    let mut terminal = ratatui::init();
+    let result = game_loop(&mut terminal, nybbler, ctx);
+    ratatui::restore();
+    result
+}
+
+fn game_loop(
+    terminal: &mut DefaultTerminal,
+    nybbler: &mut Nybbler,
+    ctx: &mut GameContext,
+) -> io::Result<()> {
+    let render = ctx.render;
+    let mut menu = ListState::default();
+    let mut message = format!("Welcome back, {}!", nybbler.display_name());
+
+    loop {
+        nybbler.update(&ctx.config);
+        if let Some(stage) = nybbler.take_milestone_announcements().pop() {
+            message = render.labeled("🎉", &format!("{} grew into {}!", nybbler.display_name(), stage.with_article()));
+            if stage == LifeStage::Adult {
+                message.push_str(" They're hardier now, so their stats fall 20% slower.");
+            }
+        }
+
+        if !nybbler.is_alive() {
+            message = render.labeled("💔", &format!(
+                "{} has passed away after {} days. Press any key to leave.",
+                nybbler.display_name(), nybbler.age
+            ));
+            terminal.draw(|frame| draw(frame, nybbler, &[], &mut menu, &message, render, &ctx.config))?;
+            wait_for_key()?;
+            return Ok(());
+        }
+
+        // The menu changes with the Nybbler's mood, so keep the selection in range
+        // Switching pets needs the classic view's prompts
+        let mut actions = nybbler.available_actions(&ctx.config);
+        actions.retain(|&action| action != Action::SwitchPet);
+        // Start where the classic menu would, on what the Nybbler needs most
+        if menu.selected().is_none() {
+            menu.select(Some(crate::suggested_action(nybbler, &actions, Local::now())));
+        }
+        if menu.selected().is_some_and(|selected| selected >= actions.len()) {
+            menu.select(Some(actions.len() - 1));
+        }
+        terminal.draw(|frame| draw(frame, nybbler, &actions, &mut menu, &message, render, &ctx.config))?;
+
+        // Poll so the stats keep refreshing while no key is pressed
+        if !event::poll(REFRESH_INTERVAL)? {
+            continue;
+        }
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => menu.select_previous(),
+            KeyCode::Down | KeyCode::Char('j') => {
+                let last = actions.len() - 1;
+                menu.select(Some((menu.selected().unwrap_or(0) + 1).min(last)));
+            }
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(nybbler.save()?),
+            KeyCode::Enter => {
+                let action = actions[menu.selected().unwrap_or(0)];
+                if let Err(error) = nybbler.try_action(action) {
+                    message = render.labeled("⏳", &error.reason(&nybbler.display_name()));
+                    continue;
+                }
+                nybbler.record(action);
+                let level_before = nybbler.level;
+                let bond_before = nybbler.bond_tier();
+                message = match action {
+                    Action::Feed => {
+                        nybbler.feed();
+                        render.labeled("🍔", &format!("You fed {} a delicious meal! \"{}\"", nybbler.display_name(), nybbler.character_type.speak(Moment::Eating)))
+                    }
+                    Action::Treat => match nybbler.treat() {
+                        (_, true) => render.labeled("🤢", &format!("Too much sugar! {} is having a sugar crash...", nybbler.display_name())),
+                        (gained, false) if gained < crate::TREAT_HAPPINESS => {
+                            render.labeled("🍬", &format!("{} nibbles the treat, but it's not as exciting anymore...", nybbler.display_name()))
+                        }
+                        _ => render.labeled("🍬", &format!("You gave {} a sweet treat!", nybbler.display_name())),
+                    },
+                    Action::Play => {
+                        nybbler.play(Toy::default());
+                        render.labeled("🎮", &format!("You played with {}! \"{}\"", nybbler.display_name(), nybbler.character_type.speak(Moment::Playing)))
+                    }
+                    Action::Sleep => {
+                        nybbler.sleep();
+                        render.labeled("💤", &format!("{} took a nap and feels refreshed! \"{}\"", nybbler.display_name(), nybbler.character_type.speak(Moment::Sleeping)))
+                    }
+                    Action::Heal => {
+                        nybbler.heal();
+                        render.labeled("💊", &format!("{} is all better now! \"{}\"", nybbler.display_name(), nybbler.character_type.speak(Moment::Healing)))
+                    }
+                    Action::Clean => {
+                        nybbler.clean();
+                        render.labeled("🧼", &format!("You gave {} a bubbly bath! Squeaky clean!", nybbler.display_name()))
+                    }
+                    Action::Comfort => {
+                        let comfort = match nybbler.mood {
+                            NybblerMood::Sick => render.labeled("🫂", &format!("You tuck {} in with a warm blanket.", nybbler.display_name())),
+                            _ => render.labeled("🫂", &format!("You give {} a big, gentle hug.", nybbler.display_name())),
+                        };
+                        if nybbler.comfort(&mut ctx.rng) {
+                            format!("{} {} perks right up!", comfort, nybbler.display_name())
+                        } else {
+                            format!("{} {} feels a little better.", comfort, nybbler.display_name())
+                        }
+                    }
+                    Action::Notes => {
+                        // Hand the terminal back to the line-based prompt while typing
+                        ratatui::restore();
+                        let notes = crate::prompt_notes(nybbler);
+                        *terminal = ratatui::init();
+                        nybbler.notes = notes?;
+                        render.labeled("📝", &format!("Notes saved for {}!", nybbler.display_name()))
+                    }
+                    Action::Help => {
+                        let help = crate::help_text(nybbler, render, &ctx.config);
+                        terminal.draw(|frame| draw_help(frame, &help, render))?;
+                        wait_for_key()?;
+                        continue;
+                    }
+                    Action::SwitchPet | Action::Exit => return Ok(nybbler.save()?),
+                };
+                if nybbler.level > level_before {
+                    message = format!("{} {}", message, crate::level_up_message(nybbler, render));
+                }
+                if nybbler.bond_tier() > bond_before {
+                    message = format!("{} {}", message, crate::bond_message(nybbler, render));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+// Block until any key is pressed
+fn wait_for_key() -> io::Result<()> {
+    loop {
+        if let Event::Key(key) = event::read()? {
+            if key.kind == KeyEventKind::Press {
+                return Ok(());
+            }
+        }
+    }
+}
+
+// Draw the help guide over the whole screen
+fn draw_help(frame: &mut Frame, help: &str, render: Render) {
+    let guide = Paragraph::new(help)
+        .wrap(Wrap { trim: false })
+        .block(Block::bordered().title(format!(" {} ", render.labeled("❓", &format!("Help {} press any key to go back", render.pick("—", "-"))))));
+    frame.render_widget(guide, frame.area());
+}
+
+// Draw the header, art, stats, menu, and message panels
+fn draw(
+    frame: &mut Frame,
+    nybbler: &Nybbler,
+    actions: &[Action],
+    menu: &mut ListState,
+    message: &str,
+    render: Render,
+    config: &Config,
+) {
+    let [header_area, body_area, message_area] = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Min(12),
+        Constraint::Length(3),
+    ])
+    .areas(frame.area());
+    let [art_area, side_area] =
+        Layout::horizontal([Constraint::Percentage(45), Constraint::Percentage(55)]).areas(body_area);
+    let [stats_area, menu_area] =
+        Layout::vertical([Constraint::Length(15), Constraint::Min(3)]).areas(side_area);
+
+    let header = Paragraph::new(Line::from(format!(
+        "{}  Age: {}, {:?}{}   {} {}",
+        render.framed("✨", &format!("{} the Nybbler", nybbler.display_name())),
+        render.age_units.format(nybbler.age),
+        nybbler.life_stage(),
+        render.pick(" 🎂", ""),
+        nybbler.mood.glyph(render),
+        nybbler.mood.message(render)
+    )))
+    .style(Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD))
+    .block(Block::bordered());
+    frame.render_widget(header, header_area);
+
+    let art = Paragraph::new(nybbler.mood_art(render))
+        .style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
+        .block(Block::bordered().title(" Nybbler "));
+    frame.render_widget(art, art_area);
+
+    let stats = [
+        (Stat::Hunger, "🍔", Color::Blue),
+        (Stat::Happiness, "🎈", Color::Magenta),
+        (Stat::Energy, "⚡", Color::Yellow),
+        (Stat::Health, "💖", Color::Red),
+        (Stat::Cleanliness, "🧼", Color::Cyan),
+    ];
+    let stat_areas = Layout::vertical([Constraint::Length(3); 5]).split(stats_area);
+    for ((stat, emoji, color), area) in stats.into_iter().zip(stat_areas.iter()) {
+        let gauge = Gauge::default()
+            .block(Block::bordered().title(render.labeled(emoji, stat.label(render))))
+            .gauge_style(Style::default().fg(color))
+            .percent(u16::from(nybbler.shown_stat(stat, render).min(100)));
+        frame.render_widget(gauge, *area);
+    }
+
+    let items: Vec<String> = actions
+        .iter()
+        .map(|&action| match nybbler.try_action(action) {
+            Err(error) => format!("{} ({})", action.display_label(render, config), error.short()),
+            Ok(()) => action.menu_label(render, config),
+        })
+        .collect();
+    let list = List::new(items)
+        .block(Block::bordered().title(format!(" {} ", render.framed("✨", "What would you like to do?"))))
+        .highlight_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+        .highlight_symbol(render.pick("❯ ", "> "));
+    frame.render_stateful_widget(list, menu_area, menu);
+
+    let message = Paragraph::new(message)
+        .style(Style::default().fg(Color::Green))
+        .block(Block::bordered().title(format!(" {} to choose, Enter to act, q to save and quit ", render.pick("↑/↓", "Up/Down"))));
+    frame.render_widget(message, message_area);
+}